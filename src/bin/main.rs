@@ -1,6 +1,9 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
 use bevy::prelude::*;
 
-use planet::Planet;
+use planet::{Planet, PlanetConfig};
 
 const NUM_POINTS: usize = 5000;
 
@@ -16,6 +19,12 @@ const SUN_INTENSITY: f32 = 1000.0;
 const AMBIENT_BRIGHTNESS: f32 = 50.0;
 
 fn main() {
+    let path = std::env::args().nth(1).map(PathBuf::from);
+    let (preset, saved) = match path {
+        Some(path) if is_saved_planet(&path) => (Preset(None), SavedPlanet(Some(path))),
+        path => (Preset(path), SavedPlanet(None)),
+    };
+
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -26,9 +35,13 @@ fn main() {
             ..default()
         }))
         .insert_resource(ClearColor(Color::BLACK))
+        .insert_resource(preset)
+        .insert_resource(saved)
         .add_systems(Startup, setup)
         .add_systems(Update, (
             reset,
+            save_preset,
+            save_planet,
             move_camera,
             move_sun,
         ))
@@ -44,6 +57,8 @@ fn setup(
     mut ambient_light: ResMut<AmbientLight>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    preset: Res<Preset>,
+    saved: Res<SavedPlanet>,
 ) {
     ambient_light.brightness = AMBIENT_BRIGHTNESS;
 
@@ -69,7 +84,17 @@ fn setup(
         ).looking_at(Vec3::ZERO, Vec3::Y),
     ));
 
-    add_planet(&mut commands, &mut meshes, &mut materials);
+    match saved.0.as_deref().map(load_planet) {
+        Some(Ok(mut planet)) => {
+            planet.render(&mut commands, &mut meshes, &mut materials);
+            commands.insert_resource(planet);
+        },
+        Some(Err(e)) => {
+            eprintln!("failed to load planet: {e}");
+            add_planet(&mut commands, &mut meshes, &mut materials, &preset);
+        },
+        None => add_planet(&mut commands, &mut meshes, &mut materials, &preset),
+    }
 
     commands.insert_resource(ViewMode::None);
     commands.insert_resource(CameraLock(true));
@@ -79,24 +104,73 @@ fn add_planet(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<StandardMaterial>,
+    preset: &Preset,
 ) {
-    let mut planet = Planet::new(NUM_POINTS);
+    let config = match &preset.0 {
+        Some(path) => PlanetConfig::load(path).unwrap_or_else(|e| {
+            eprintln!("failed to load preset {}: {e}", path.display());
+            PlanetConfig::default()
+        }),
+        None => PlanetConfig::default(),
+    };
+    let mut planet = Planet::new(NUM_POINTS, config);
     planet.render(commands, meshes, materials);
     commands.insert_resource(planet);
 }
 
+fn is_saved_planet(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("planet") | Some("json"),
+    )
+}
+
+fn load_planet(path: &Path) -> io::Result<Planet> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => Planet::load_json(path),
+        _ => Planet::load(path),
+    }
+}
+
 fn reset(
     mut commands: Commands,
     entities: Query<Entity, With<Mesh3d>>,
     keys: Res<ButtonInput<KeyCode>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    preset: Res<Preset>,
 ) {
     if keys.just_pressed(KeyCode::Backslash) {
         for entity in entities {
             commands.entity(entity).despawn();
         }
-        add_planet(&mut commands, &mut meshes, &mut materials);
+        add_planet(&mut commands, &mut meshes, &mut materials, &preset);
+    }
+}
+
+fn save_preset(
+    keys: Res<ButtonInput<KeyCode>>,
+    planet: Res<Planet>,
+) {
+    if keys.just_pressed(KeyCode::KeyP) {
+        let path = format!("planet-{}.ron", planet.seed);
+        match planet.config.save(&path) {
+            Ok(()) => println!("saved preset to {path}"),
+            Err(e) => eprintln!("failed to save preset {path}: {e}"),
+        }
+    }
+}
+
+fn save_planet(
+    keys: Res<ButtonInput<KeyCode>>,
+    planet: Res<Planet>,
+) {
+    if keys.just_pressed(KeyCode::KeyO) {
+        let path = format!("planet-{}.planet", planet.seed);
+        match planet.save(&path) {
+            Ok(()) => println!("saved planet to {path}"),
+            Err(e) => eprintln!("failed to save planet {path}: {e}"),
+        }
     }
 }
 
@@ -202,6 +276,7 @@ fn draw_gizmos(
         ViewMode::Ocean => &planet.ocean_currents,
         ViewMode::Atmosphere => &planet.atmospheric_currents,
         ViewMode::Precipitation => &planet.atmospheric_currents,
+        ViewMode::Plates => &planet.plate_velocities,
         _ => &vec![],
     };
 
@@ -218,15 +293,66 @@ fn draw_gizmos(
         _ => 1.0,
     };
 
+    let length = match *view_mode {
+        ViewMode::Plates => 5.0,
+        _ => 200.0,
+    };
+
     if !velocities.is_empty() {
         for p in 0..planet.points.len() {
             let start = 100.0 * height * planet.points[p].normalize();
-            let end = start + 200.0 * velocities[p];
-            gizmos.arrow(start, end, Color::linear_rgb(
-                sign * temperatures[p], 
-                0.0, 
-                -sign * temperatures[p],
-            )).with_tip_length(0.5);
+            let end = start + length * velocities[p];
+            let color = match *view_mode {
+                ViewMode::Plates => {
+                    let [r, g, b] = planet.plates[planet.plate_ids[p]].color;
+                    Color::linear_rgb(r, g, b)
+                },
+                _ => Color::linear_rgb(
+                    sign * temperatures[p], 
+                    0.0, 
+                    -sign * temperatures[p],
+                ),
+            };
+            gizmos.arrow(start, end, color).with_tip_length(0.5);
+        }
+    }
+
+    if *view_mode == ViewMode::Plates {
+        for boundary in &planet.boundaries {
+            let [r, g, b] = boundary.kind.color();
+            gizmos.line(
+                105.0 * boundary.start, 
+                105.0 * boundary.end, 
+                Color::linear_rgb(r, g, b),
+            );
+        }
+
+        for volcano in &planet.volcanoes {
+            let color = if volcano.active() {
+                Color::linear_rgb(1.0, 0.3, 0.0)
+            } else {
+                Color::linear_rgb(0.3, 0.3, 0.3)
+            };
+            let position = 105.0 * planet.points[volcano.cell].normalize();
+            gizmos.sphere(Isometry3d::from_translation(position), 0.5, color);
+        }
+    }
+
+    if *view_mode == ViewMode::Rivers {
+        for river in &planet.rivers {
+            let points = river.iter().map(|p| 100.2 * planet.points[*p]);
+            gizmos.linestrip(points, Color::linear_rgb(0.1, 0.4, 1.0));
+        }
+    }
+
+    if *view_mode == ViewMode::Climate {
+        for p in 0..planet.points.len() {
+            if let Some(zone) = planet.climate_zone[p] {
+                let [r, g, b] = zone.color();
+                let color = Color::linear_rgb(r, g, b);
+                let position = 100.5 * planet.points[p];
+                gizmos.sphere(Isometry3d::from_translation(position), 1.0, color);
+            }
         }
     }
 
@@ -244,6 +370,12 @@ fn switch_view_mode(
         set_view_mode(&mut view_mode, ViewMode::Atmosphere)
     } else if keys.just_pressed(KeyCode::Digit3) {
         set_view_mode(&mut view_mode, ViewMode::Precipitation)
+    } else if keys.just_pressed(KeyCode::Digit4) {
+        set_view_mode(&mut view_mode, ViewMode::Plates)
+    } else if keys.just_pressed(KeyCode::Digit5) {
+        set_view_mode(&mut view_mode, ViewMode::Rivers)
+    } else if keys.just_pressed(KeyCode::Digit6) {
+        set_view_mode(&mut view_mode, ViewMode::Climate)
     }
 }
 
@@ -264,7 +396,16 @@ enum ViewMode {
     Ocean,
     Atmosphere,
     Precipitation,
+    Plates,
+    Rivers,
+    Climate,
 }
 
 #[derive(Resource)]
-struct CameraLock(bool);
\ No newline at end of file
+struct CameraLock(bool);
+
+#[derive(Resource)]
+struct Preset(Option<PathBuf>);
+
+#[derive(Resource)]
+struct SavedPlanet(Option<PathBuf>);
\ No newline at end of file
@@ -0,0 +1,297 @@
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use planet::{Planet, PlanetConfig};
+use planet::export::GltfOptions;
+use planet::map::{Equirectangular, Icosahedral, Interpolation, Mollweide, Orthographic};
+
+const NUM_POINTS: usize = 5000;
+const OUTPUT: &str = "planet";
+const NORMAL_STRENGTH: f32 = 10.0;
+
+const USAGE: &str = "\
+usage: planet-gen [options]
+
+options:
+    --seed <u64>       seed to generate from (random if omitted)
+    --points <n>       number of cells on the sphere (default 5000)
+    --config <path>    RON preset to generate with (default config if omitted)
+    --output <dir>     directory to write into (default ./planet)
+    --json             also write the planet as JSON
+    --maps <width>     also write PNG maps of this width
+    --projection <p>   equirectangular (default), mollweide, orthographic or icosahedral
+    --cube-map <size>  also bake six cube map faces of this resolution
+    --help             print this message";
+
+struct Arguments {
+    seed: Option<u64>,
+    num_points: usize,
+    config: Option<PathBuf>,
+    output: PathBuf,
+    json: bool,
+    map_width: Option<u32>,
+    projection: Projection,
+    cube_map_resolution: Option<u32>,
+}
+
+#[derive(Clone, Copy)]
+enum Projection {
+    Equirectangular,
+    Mollweide,
+    Orthographic,
+    Icosahedral,
+}
+
+impl std::str::FromStr for Projection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "equirectangular" => Ok(Self::Equirectangular),
+            "mollweide" => Ok(Self::Mollweide),
+            "orthographic" => Ok(Self::Orthographic),
+            "icosahedral" => Ok(Self::Icosahedral),
+            _ => Err(()),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let arguments = match arguments() {
+        Ok(Some(arguments)) => arguments,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+
+    match generate(&arguments) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("planet-gen: {e}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn arguments() -> Result<Option<Arguments>, String> {
+    let mut arguments = Arguments {
+        seed: None,
+        num_points: NUM_POINTS,
+        config: None,
+        output: PathBuf::from(OUTPUT),
+        json: false,
+        map_width: None,
+        projection: Projection::Equirectangular,
+        cube_map_resolution: None,
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => arguments.seed = Some(parse(&arg, args.next())?),
+            "--points" => arguments.num_points = parse(&arg, args.next())?,
+            "--config" => arguments.config = Some(parse(&arg, args.next())?),
+            "--output" => arguments.output = parse(&arg, args.next())?,
+            "--json" => arguments.json = true,
+            "--maps" => arguments.map_width = Some(parse(&arg, args.next())?),
+            "--projection" => arguments.projection = parse(&arg, args.next())?,
+            "--cube-map" => arguments.cube_map_resolution = Some(parse(&arg, args.next())?),
+            "--help" | "-h" => return Ok(None),
+            _ => return Err(format!("unknown argument {arg}")),
+        }
+    }
+
+    return Ok(Some(arguments));
+}
+
+fn parse<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{flag} needs a value"))?;
+    value.parse().map_err(|_| format!("invalid value {value} for {flag}"))
+}
+
+fn generate(arguments: &Arguments) -> io::Result<()> {
+    let config = match &arguments.config {
+        Some(path) => PlanetConfig::load(path)?,
+        None => PlanetConfig::default(),
+    };
+    let seed = arguments.seed.unwrap_or_else(rand::random);
+    let planet = Planet::from_seed(seed, arguments.num_points, config);
+
+    let output = &arguments.output;
+    fs::create_dir_all(output)?;
+
+    planet.save(output.join("planet.planet"))?;
+    if arguments.json {
+        planet.save_json(output.join("planet.json"))?;
+    }
+    planet.config.save(output.join("config.ron"))?;
+    planet.export_glb(output.join("planet.glb"), &GltfOptions::default())?;
+    planet.export_obj(output.join("planet.obj"))?;
+    planet.export_ply(output.join("planet.ply"))?;
+    if let Some(width) = arguments.map_width {
+        let directory = output.join("maps");
+        let interpolation = Interpolation::Barycentric;
+        match arguments.projection {
+            Projection::Equirectangular => {
+                planet.save_maps(directory, &Equirectangular, width, interpolation)?
+            },
+            Projection::Mollweide => {
+                planet.save_maps(directory, &Mollweide, width, interpolation)?
+            },
+            Projection::Orthographic => {
+                planet.save_maps(directory, &Orthographic::default(), width, interpolation)?
+            },
+            Projection::Icosahedral => {
+                planet.save_maps(directory, &Icosahedral::new(), width, interpolation)?
+            },
+        }
+    }
+    if let Some(resolution) = arguments.cube_map_resolution {
+        let directory = output.join("cube");
+        planet.save_cube_maps(directory, resolution, Interpolation::Barycentric, NORMAL_STRENGTH)?;
+    }
+    write_triangles(&planet, File::create(output.join("triangles.csv"))?)?;
+    write_cells(&planet, File::create(output.join("cells.csv"))?)?;
+
+    let summary = summary(&planet);
+    fs::write(output.join("summary.txt"), &summary)?;
+    print!("{summary}");
+
+    return Ok(());
+}
+
+fn write_triangles(planet: &Planet, file: File) -> io::Result<()> {
+    let mut writer = BufWriter::new(file);
+    writeln!(writer, "a,b,c")?;
+    for t in planet.triangles.chunks(3) {
+        writeln!(writer, "{},{},{}", t[0], t[1], t[2])?;
+    }
+    writer.flush()
+}
+
+fn write_cells(planet: &Planet, file: File) -> io::Result<()> {
+    let mut writer = BufWriter::new(file);
+    writeln!(
+        writer, 
+        "x,y,z,terrain,biome,elevation,surface_height,\
+        ocean_current_x,ocean_current_y,ocean_current_z,ocean_temperature,\
+        atmospheric_current_x,atmospheric_current_y,atmospheric_current_z,atmospheric_temperature,\
+        precipitation,climate_zone",
+    )?;
+    for p in 0..planet.points.len() {
+        let point = planet.points[p];
+        let ocean_current = planet.ocean_currents[p];
+        let atmospheric_current = planet.atmospheric_currents[p];
+        writeln!(
+            writer,
+            "{},{},{},{:?},{:?},{},{},{},{},{},{},{},{},{},{},{},{}",
+            point.x, point.y, point.z,
+            planet.terrain[p],
+            planet.biomes[p],
+            planet.elevation[p],
+            planet.surface_height[p],
+            ocean_current.x, ocean_current.y, ocean_current.z,
+            planet.ocean_temperatures[p],
+            atmospheric_current.x, atmospheric_current.y, atmospheric_current.z,
+            planet.atmospheric_temperatures[p],
+            planet.precipitation[p],
+            planet.climate_zone[p].map_or(String::new(), |zone| format!("{zone:?}")),
+        )?;
+    }
+    writer.flush()
+}
+
+fn summary(planet: &Planet) -> String {
+    let num_points = planet.points.len();
+    let mut summary = format!("seed: {}\npoints: {num_points}\n", planet.seed);
+
+    summary += "\nterrain:\n";
+    for (terrain, count) in counts(&planet.terrain) {
+        summary += &format!("    {terrain:?}: {count} ({:.1}%)\n", percent(count, num_points));
+    }
+
+    summary += "\nbiomes:\n";
+    for (biome, count) in counts(&planet.biomes) {
+        summary += &format!("    {biome:?}: {count} ({:.1}%)\n", percent(count, num_points));
+    }
+
+    summary += "\nclimate zones:\n";
+    let zones: Vec<_> = planet.climate_zone.iter().flatten().copied().collect();
+    for (zone, count) in counts(&zones) {
+        summary += &format!("    {zone:?}: {count} ({:.1}%)\n", percent(count, zones.len()));
+    }
+
+    let continental = planet.plates.iter().filter(|plate| plate.continental).count();
+    summary += &format!("\nplates: {} ({continental} continental)\n", planet.plates.len());
+    let kinds: Vec<_> = planet.boundaries.iter().map(|boundary| boundary.kind).collect();
+    for (kind, count) in counts(&kinds) {
+        summary += &format!("    {kind:?} boundaries: {count}\n");
+    }
+
+    let endorheic = planet.lakes.iter().filter(|lake| lake.endorheic).count();
+    summary += &format!(
+        "\nrivers: {}\nlakes: {} ({endorheic} endorheic)\n",
+        planet.rivers.len(),
+        planet.lakes.len(),
+    );
+
+    summary += "\nclimate (min / mean / max):\n";
+    for (name, values) in [
+        ("ocean temperature", &planet.ocean_temperatures),
+        ("atmospheric temperature", &planet.atmospheric_temperatures),
+        ("precipitation", &planet.precipitation),
+    ] {
+        let (min, mean, max) = statistics(values);
+        summary += &format!("    {name}: {min:.3} / {mean:.3} / {max:.3}\n");
+    }
+
+    let units = &planet.config.units;
+    let temperatures: Vec<f32> = (0..num_points)
+        .map(|p| planet.temperature_celsius(p))
+        .collect();
+    let rainfall: Vec<f32> = planet.precipitation.iter()
+        .map(|p| units.millimetres_per_year(*p))
+        .collect();
+    summary += "\nphysical units (min / mean / max):\n";
+    for (name, unit, values) in [
+        ("elevation", "m", &planet.elevation),
+        ("temperature", "°C", &temperatures),
+        ("rainfall", "mm/yr", &rainfall),
+    ] {
+        let (min, mean, max) = statistics(values);
+        summary += &format!("    {name}: {min:.0} / {mean:.0} / {max:.0} {unit}\n");
+    }
+
+    return summary;
+}
+
+fn counts<T: PartialEq + Copy>(values: &Vec<T>) -> Vec<(T, usize)> {
+    let mut counts: Vec<(T, usize)> = vec![];
+    for value in values {
+        match counts.iter_mut().find(|(v, _)| v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*value, 1)),
+        }
+    }
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+    return counts;
+}
+
+fn percent(count: usize, total: usize) -> f32 {
+    100.0 * count as f32 / total as f32
+}
+
+fn statistics(values: &Vec<f32>) -> (f32, f32, f32) {
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    (min, mean, max)
+}
\ No newline at end of file
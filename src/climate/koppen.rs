@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+
+use crate::climate::MONTHS;
+
+/// A Köppen–Geiger climate zone, named by its code.
+///
+/// The first letter is the main group: tropical (A), arid (B), temperate
+/// (C), continental (D) or polar (E). For arid climates the second letter
+/// is desert (W) or steppe (S) and the third hot (h) or cold (k). For the
+/// others the second letter is the dry season: summer (s), winter (w) or
+/// none (f, m for monsoon), and the third how warm the summer is, from
+/// hot (a) to very cold winters (d).
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClimateZone {
+    /// Tropical rainforest.
+    Af,
+    /// Tropical monsoon.
+    Am,
+    /// Tropical savanna.
+    Aw,
+    /// Hot desert.
+    BWh,
+    /// Cold desert.
+    BWk,
+    /// Hot steppe.
+    BSh,
+    /// Cold steppe.
+    BSk,
+    /// Hot-summer Mediterranean.
+    Csa,
+    /// Warm-summer Mediterranean.
+    Csb,
+    /// Cold-summer Mediterranean.
+    Csc,
+    /// Monsoon-influenced humid subtropical.
+    Cwa,
+    /// Subtropical highland.
+    Cwb,
+    /// Cold subtropical highland.
+    Cwc,
+    /// Humid subtropical.
+    Cfa,
+    /// Oceanic.
+    Cfb,
+    /// Subpolar oceanic.
+    Cfc,
+    Dsa,
+    Dsb,
+    Dsc,
+    Dsd,
+    Dwa,
+    Dwb,
+    Dwc,
+    Dwd,
+    /// Hot-summer humid continental.
+    Dfa,
+    /// Warm-summer humid continental.
+    Dfb,
+    /// Subarctic.
+    Dfc,
+    /// Extremely cold subarctic.
+    Dfd,
+    /// Tundra.
+    ET,
+    /// Ice cap.
+    EF,
+}
+
+impl ClimateZone {
+    /// The color the zone is usually drawn with on Köppen–Geiger maps.
+    pub const fn color(&self) -> [f32; 3] {
+        let [r, g, b] = match self {
+            Self::Af => [0, 0, 255],
+            Self::Am => [0, 120, 255],
+            Self::Aw => [70, 170, 250],
+            Self::BWh => [255, 0, 0],
+            Self::BWk => [255, 150, 150],
+            Self::BSh => [245, 165, 0],
+            Self::BSk => [255, 220, 100],
+            Self::Csa => [255, 255, 0],
+            Self::Csb => [200, 200, 0],
+            Self::Csc => [150, 150, 0],
+            Self::Cwa => [150, 255, 150],
+            Self::Cwb => [100, 200, 100],
+            Self::Cwc => [50, 150, 50],
+            Self::Cfa => [200, 255, 80],
+            Self::Cfb => [100, 255, 80],
+            Self::Cfc => [50, 200, 0],
+            Self::Dsa => [255, 0, 255],
+            Self::Dsb => [200, 0, 200],
+            Self::Dsc => [150, 50, 150],
+            Self::Dsd => [150, 100, 150],
+            Self::Dwa => [170, 175, 255],
+            Self::Dwb => [90, 120, 220],
+            Self::Dwc => [75, 80, 180],
+            Self::Dwd => [50, 0, 135],
+            Self::Dfa => [0, 255, 255],
+            Self::Dfb => [55, 200, 255],
+            Self::Dfc => [0, 125, 125],
+            Self::Dfd => [0, 70, 95],
+            Self::ET => [178, 178, 178],
+            Self::EF => [102, 102, 102],
+        };
+        [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0]
+    }
+}
+
+enum DrySeason {
+    Summer,
+    Winter,
+    None,
+}
+
+enum Summer {
+    Hot,
+    Warm,
+    Cool,
+    /// A short summer with a bitterly cold winter.
+    Cold,
+}
+
+/// Classifies a climate from its monthly temperatures in °C and rainfall in
+/// mm, following the thresholds of Peel, Finlayson & McMahon (2007).
+pub fn koppen(
+    temperatures: &[f32; MONTHS],
+    rainfall: &[f32; MONTHS],
+    summer: &[bool; MONTHS],
+) -> ClimateZone {
+    let mean = temperatures.iter().sum::<f32>() / MONTHS as f32;
+    let hottest = temperatures.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let coldest = temperatures.iter().copied().fold(f32::INFINITY, f32::min);
+    let warm_months = temperatures.iter().filter(|t| **t >= 10.0).count();
+
+    let total: f32 = rainfall.iter().sum();
+    let driest = rainfall.iter().copied().fold(f32::INFINITY, f32::min);
+    let mut summer_total = 0.0;
+    let mut summer_driest = f32::INFINITY;
+    let mut summer_wettest: f32 = 0.0;
+    let mut winter_total = 0.0;
+    let mut winter_driest = f32::INFINITY;
+    let mut winter_wettest: f32 = 0.0;
+    for month in 0..MONTHS {
+        let rain = rainfall[month];
+        if summer[month] {
+            summer_total += rain;
+            summer_driest = summer_driest.min(rain);
+            summer_wettest = summer_wettest.max(rain);
+        } else {
+            winter_total += rain;
+            winter_driest = winter_driest.min(rain);
+            winter_wettest = winter_wettest.max(rain);
+        }
+    }
+
+    if hottest < 10.0 {
+        return if hottest > 0.0 { ClimateZone::ET } else { ClimateZone::EF };
+    }
+
+    // Ten times Peel's dryness threshold of 2 × the mean temperature, plus
+    // 28 or 14 mm when the rain falls mostly in summer or year-round.
+    let threshold = if winter_total >= 0.7 * total {
+        20.0 * mean
+    } else if summer_total >= 0.7 * total {
+        20.0 * mean + 280.0
+    } else {
+        20.0 * mean + 140.0
+    };
+    if total < threshold {
+        let desert = total < 0.5 * threshold;
+        let hot = mean >= 18.0;
+        return match (desert, hot) {
+            (true, true) => ClimateZone::BWh,
+            (true, false) => ClimateZone::BWk,
+            (false, true) => ClimateZone::BSh,
+            (false, false) => ClimateZone::BSk,
+        };
+    }
+
+    if coldest >= 18.0 {
+        return if driest >= 60.0 {
+            ClimateZone::Af
+        } else if driest >= 100.0 - total / 25.0 {
+            ClimateZone::Am
+        } else {
+            ClimateZone::Aw
+        };
+    }
+
+    let dry_season = if summer_driest < 40.0 && summer_driest < winter_wettest / 3.0 {
+        DrySeason::Summer
+    } else if winter_driest < summer_wettest / 10.0 {
+        DrySeason::Winter
+    } else {
+        DrySeason::None
+    };
+    let warmth = if hottest >= 22.0 {
+        Summer::Hot
+    } else if warm_months >= 4 {
+        Summer::Warm
+    } else if coldest < -38.0 {
+        Summer::Cold
+    } else {
+        Summer::Cool
+    };
+
+    if coldest > 0.0 {
+        match (dry_season, warmth) {
+            (DrySeason::Summer, Summer::Hot) => ClimateZone::Csa,
+            (DrySeason::Summer, Summer::Warm) => ClimateZone::Csb,
+            (DrySeason::Summer, _) => ClimateZone::Csc,
+            (DrySeason::Winter, Summer::Hot) => ClimateZone::Cwa,
+            (DrySeason::Winter, Summer::Warm) => ClimateZone::Cwb,
+            (DrySeason::Winter, _) => ClimateZone::Cwc,
+            (DrySeason::None, Summer::Hot) => ClimateZone::Cfa,
+            (DrySeason::None, Summer::Warm) => ClimateZone::Cfb,
+            (DrySeason::None, _) => ClimateZone::Cfc,
+        }
+    } else {
+        match (dry_season, warmth) {
+            (DrySeason::Summer, Summer::Hot) => ClimateZone::Dsa,
+            (DrySeason::Summer, Summer::Warm) => ClimateZone::Dsb,
+            (DrySeason::Summer, Summer::Cool) => ClimateZone::Dsc,
+            (DrySeason::Summer, Summer::Cold) => ClimateZone::Dsd,
+            (DrySeason::Winter, Summer::Hot) => ClimateZone::Dwa,
+            (DrySeason::Winter, Summer::Warm) => ClimateZone::Dwb,
+            (DrySeason::Winter, Summer::Cool) => ClimateZone::Dwc,
+            (DrySeason::Winter, Summer::Cold) => ClimateZone::Dwd,
+            (DrySeason::None, Summer::Hot) => ClimateZone::Dfa,
+            (DrySeason::None, Summer::Warm) => ClimateZone::Dfb,
+            (DrySeason::None, Summer::Cool) => ClimateZone::Dfc,
+            (DrySeason::None, Summer::Cold) => ClimateZone::Dfd,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// April to September, the summer of a northern station.
+    const NORTHERN_SUMMER: [bool; MONTHS] = [
+        false, false, false, true, true, true, true, true, true, false, false, false,
+    ];
+
+    #[test]
+    fn london_is_oceanic() {
+        let temperatures = [5.2, 5.3, 7.6, 9.9, 13.3, 16.5, 18.7, 18.5, 15.7, 12.0, 8.0, 5.5];
+        let rainfall = [55.0, 41.0, 42.0, 44.0, 49.0, 45.0, 45.0, 50.0, 49.0, 69.0, 59.0, 55.0];
+        assert_eq!(koppen(&temperatures, &rainfall, &NORTHERN_SUMMER), ClimateZone::Cfb);
+    }
+
+    #[test]
+    fn cairo_is_hot_desert() {
+        let temperatures = [14.0, 15.1, 17.6, 21.2, 24.9, 27.3, 28.0, 28.0, 26.2, 23.5, 19.1, 15.5];
+        let rainfall = [5.0, 4.0, 3.0, 1.0, 0.5, 0.0, 0.0, 0.0, 0.0, 1.0, 3.0, 6.0];
+        assert_eq!(koppen(&temperatures, &rainfall, &NORTHERN_SUMMER), ClimateZone::BWh);
+    }
+
+    #[test]
+    fn singapore_is_tropical_rainforest() {
+        let temperatures = [26.5, 27.1, 27.5, 28.0, 28.3, 28.3, 27.9, 27.9, 27.6, 27.6, 27.0, 26.4];
+        let rainfall = [
+            243.0, 160.0, 183.0, 179.0, 172.0, 162.0, 158.0, 176.0, 169.0, 194.0, 256.0, 288.0,
+        ];
+        assert_eq!(koppen(&temperatures, &rainfall, &NORTHERN_SUMMER), ClimateZone::Af);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,112 @@
+use std::f32::consts::TAU;
+
+use bevy::math::Vec3;
+
+use crate::config::{ClimateConfig, UnitsConfig};
+use crate::terrain::Terrain;
+use crate::weather::temperatures::{land_temperature, sea_temperature};
+
+pub use koppen::ClimateZone;
+use koppen::koppen;
+
+mod koppen;
+
+pub const MONTHS: usize = 12;
+
+/// How far into the year, in months, the northern spring equinox falls.
+const EQUINOX: f32 = 2.7;
+
+pub struct Climate {
+    /// The mean temperature of each month in °C.
+    pub temperatures: Vec<[f32; MONTHS]>,
+    /// The rainfall of each month in mm.
+    pub rainfall: Vec<[f32; MONTHS]>,
+    /// The Köppen–Geiger zone of each cell not covered by the sea.
+    pub zones: Vec<Option<ClimateZone>>,
+}
+
+/// Spreads each cell's year-round temperature and precipitation over the
+/// months, then classifies the cells on land into Köppen–Geiger zones.
+///
+/// The axial tilt moves the latitude the sun is overhead between the
+/// tropics over the year. Each month's temperature is shifted by how much
+/// more or less sunlight the latitude gets than at the equinox, damped over
+/// the sea, so the tropics stay warm all year and the seasons grow towards
+/// the poles. The rain belts move with the sun in the same way, so the year's
+/// rainfall falls mostly in the months a cell sits under one.
+pub fn climate(
+    points: &Vec<Vec3>,
+    terrain: &Vec<Terrain>,
+    ocean_temperatures: &Vec<f32>,
+    atmospheric_temperatures: &Vec<f32>,
+    precipitation: &Vec<f32>,
+    config: &ClimateConfig,
+    units: &UnitsConfig,
+) -> Climate {
+    let tilt = config.axial_tilt.to_radians();
+    let declinations: [f32; MONTHS] = std::array::from_fn(|month| {
+        tilt * (TAU * (month as f32 + 0.5 - EQUINOX) / MONTHS as f32).sin()
+    });
+
+    let mut temperatures = vec![];
+    let mut rainfall = vec![];
+    let mut zones = vec![];
+    for p in 0..points.len() {
+        let y = points[p].y;
+        let latitude = y.clamp(-1.0, 1.0).asin();
+        let ocean = terrain[p].is_ocean();
+
+        let (yearly, seasonality) = if ocean {
+            let yearly = sea_temperature(y, ocean_temperatures[p], atmospheric_temperatures[p]);
+            (yearly, config.ocean_seasonality)
+        } else {
+            (land_temperature(y, atmospheric_temperatures[p]), config.land_seasonality)
+        };
+        let shifts = anomalies(declinations.map(|d| insolation(latitude, d)));
+        let monthly_temperature = shifts.map(|shift| {
+            units.celsius(yearly + seasonality * shift)
+        });
+
+        let wetness = anomalies(declinations.map(|d| rain_belt(latitude - d)));
+        let weights = wetness.map(|w| (1.0 + config.monsoon * w).max(0.0));
+        let total: f32 = weights.iter().sum();
+        let yearly_rainfall = units.millimetres_per_year(precipitation[p]);
+        let monthly_rainfall = weights.map(|w| yearly_rainfall * w / total);
+
+        let summer = declinations.map(|d| d * latitude.signum() > 0.0);
+        zones.push(if ocean {
+            None
+        } else {
+            Some(koppen(&monthly_temperature, &monthly_rainfall, &summer))
+        });
+        temperatures.push(monthly_temperature);
+        rainfall.push(monthly_rainfall);
+    }
+
+    return Climate {
+        temperatures,
+        rainfall,
+        zones,
+    };
+}
+
+/// How far the sunlight reaching a latitude rises above its yearly mean
+/// when the sun is overhead at `declination`, in dimensionless temperature.
+/// This is the part of the day's insolation that changes sign with the
+/// season, which vanishes at the equator.
+fn insolation(latitude: f32, declination: f32) -> f32 {
+    2.0 * latitude.sin() * declination.sin()
+}
+
+/// How wet the rain belts keep a latitude: wettest under the tropical rain
+/// belt at the equator and the storm tracks at 60°, driest under the
+/// subtropical highs at 30° and at the poles.
+fn rain_belt(latitude: f32) -> f32 {
+    (6.0 * latitude).cos()
+}
+
+/// Each month's difference from the mean over the year.
+fn anomalies(values: [f32; MONTHS]) -> [f32; MONTHS] {
+    let mean = values.iter().sum::<f32>() / MONTHS as f32;
+    values.map(|value| value - mean)
+}
\ No newline at end of file
@@ -0,0 +1,475 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::Biome;
+
+/// Every tuning parameter used while generating a [`crate::Planet`].
+///
+/// `PlanetConfig::default()` holds the values the generator was tuned with.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanetConfig {
+    pub tectonics: TectonicsConfig,
+    pub terrain: TerrainConfig,
+    pub erosion: ErosionConfig,
+    pub hydrology: HydrologyConfig,
+    pub biomes: BiomeConfig,
+    pub weather: WeatherConfig,
+    pub climate: ClimateConfig,
+    pub units: UnitsConfig,
+}
+
+/// Plate generation and the heights derived from plate stress.
+///
+/// Heights are measured as a fraction of the planet's radius.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TectonicsConfig {
+    /// How many plates to grow. Must be at least one.
+    pub num_plates: usize,
+    /// The fraction of the surface to cover with continental plates. Their
+    /// edges slope into the sea, so slightly less than this ends up as land.
+    pub continental_fraction: f32,
+    /// Keeps the plate nearest a pole oceanic, leaving open polar seas.
+    pub oceanic_poles: bool,
+    pub ocean_height: f32,
+    pub island_height: f32,
+    /// How far oceanic crust too low to form islands sinks per unit of
+    /// compression, carving trenches where plates converge.
+    pub trench_depth: f32,
+    pub max_height: f32,
+    pub continent_height: f32,
+    pub continent_slope: f32,
+    /// The noise added to the heights of continental plates.
+    pub continental_noise: NoiseConfig,
+    /// The noise added to the heights of oceanic plates.
+    pub oceanic_noise: NoiseConfig,
+    pub convergent_stress: f32,
+    pub divergent_stress: f32,
+    pub ocean_stress: f32,
+    /// How many times faster than they converge or diverge two plates must
+    /// slide past each other for their boundary to count as a transform.
+    pub transform_ratio: f32,
+    /// How many steps to move the plates for after they are generated. With
+    /// no steps the plates stay where they formed.
+    pub steps: usize,
+    /// How far the fastest plates move each step, in cell widths.
+    pub step_distance: f32,
+    /// The height added where two continents collide, per step.
+    pub collision_uplift: f32,
+    /// The height added to crust that another plate subducts under, per step.
+    pub subduction_uplift: f32,
+    /// The height of new crust formed where plates rift apart.
+    pub rift_height: f32,
+    /// How quickly oceanic crust sinks back towards `ocean_height` each step.
+    pub subsidence: f32,
+    /// How many mantle hotspots raise volcanic island chains.
+    pub hotspots: usize,
+    /// The height a hotspot adds to the cell above it.
+    pub hotspot_height: f32,
+    /// The fraction of a volcano's height added to the cells around it.
+    pub hotspot_flank: f32,
+    /// The most volcanoes in each hotspot's chain.
+    pub chain_length: usize,
+    /// The distance between volcanoes in a chain, in cell widths.
+    pub chain_spacing: f32,
+    /// How much lower each volcano is than the younger one before it.
+    pub chain_decay: f32,
+}
+
+/// How a layer of fractal noise is built up from octaves of Perlin noise.
+///
+/// A single octave with no warping is plain Perlin noise.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoiseConfig {
+    pub kind: NoiseKind,
+    /// The frequency of the first octave on the unit sphere.
+    pub frequency: f64,
+    /// The height of the noise, as a fraction of the planet's radius.
+    pub amplitude: f32,
+    pub octaves: usize,
+    /// How much the frequency grows with each octave.
+    pub lacunarity: f64,
+    /// How much the amplitude shrinks with each octave.
+    pub persistence: f64,
+    /// How far the sample point is displaced by another layer of noise
+    /// before sampling, which twists features into more natural shapes.
+    pub warp: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoiseKind {
+    /// Fractal Brownian motion, for rolling terrain.
+    Fbm,
+    /// Ridged multifractal noise, for sharp mountain ranges.
+    Ridged,
+}
+
+/// Height cut-offs used to classify each cell's [`crate::Terrain`]. Each
+/// terrain covers the heights above its cut-off and below the next.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TerrainConfig {
+    pub mountain_height: f32,
+    pub highland_height: f32,
+    pub hills_height: f32,
+    pub land_height: f32,
+    #[serde(alias = "shallow_height")]
+    pub shelf_height: f32,
+    pub slope_height: f32,
+    /// Anything deeper than this is a trench.
+    pub trench_height: f32,
+}
+
+/// Erosion of the heights produced by the tectonics.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErosionConfig {
+    /// How many times water is routed over the land. Zero disables
+    /// hydraulic erosion.
+    pub hydraulic_iterations: usize,
+    /// How much sediment water can carry per unit of slope, scaled by the
+    /// square root of its volume.
+    pub capacity: f32,
+    /// The fraction of its spare capacity that water erodes from a cell.
+    pub erosion_rate: f32,
+    /// The fraction of its excess sediment that water drops on a cell.
+    pub deposition_rate: f32,
+    /// The highest sediment carried into the sea can build a delta, so river
+    /// mouths stay below the land height.
+    pub delta_height: f32,
+    /// How many times material slides down over-steep slopes. Zero disables
+    /// thermal erosion.
+    pub thermal_iterations: usize,
+    /// The steepest stable slope, as height over distance between cells.
+    pub talus: f32,
+    /// The fraction of the excess slope that slides each iteration.
+    pub thermal_rate: f32,
+}
+
+/// How water drains over the land.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HydrologyConfig {
+    /// The flow a cell needs to carry a river, multiplied by the number of
+    /// points, since a catchment covers more cells the more points there
+    /// are.
+    pub river_threshold: f32,
+    /// How far a depression must be filled, as a fraction of the planet's
+    /// radius, to count as a lake.
+    pub lake_depth: f32,
+    /// The water each lake cell loses to evaporation, as a multiple of the
+    /// mean precipitation on land. Lakes whose inflow is less than this
+    /// never reach the sea.
+    pub lake_evaporation: f32,
+}
+
+/// Temperature and precipitation thresholds used to pick each cell's
+/// [`Biome`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BiomeConfig {
+    /// The Whittaker diagram used for land, checked in order. Each cell
+    /// takes the first biome whose limits it falls under, or the last
+    /// biome if it falls under none.
+    pub table: Vec<BiomeRule>,
+    /// Mountains colder than this are covered in snow.
+    pub snowline_temperature: f32,
+    /// Highlands colder than this are above the tree line.
+    pub alpine_temperature: f32,
+    /// Flat land draining more than this, multiplied by the number of
+    /// points, floods into wetland.
+    pub wetland_flow: f32,
+    /// Coasts warmer than this grow mangroves.
+    pub mangrove_temperature: f32,
+    /// Warm coasts also need more precipitation than this to grow mangroves,
+    /// so dry tropical coasts stay desert or savanna.
+    pub mangrove_precipitation: f32,
+    /// Shelf seas warmer than this grow coral reefs.
+    pub coral_temperature: f32,
+    /// Seas colder than this freeze over in winter.
+    pub sea_ice_temperature: f32,
+    /// Seas colder than this stay frozen all year.
+    pub pack_ice_temperature: f32,
+}
+
+/// One entry in the biome table, covering the cells colder and drier than
+/// its limits that no earlier entry covers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BiomeRule {
+    pub biome: Biome,
+    pub max_temperature: f32,
+    pub max_precipitation: f32,
+}
+
+/// The weather simulation. Iteration counts are each multiplied by the
+/// square root of the number of points.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeatherConfig {
+    pub ocean_iterations: f32,
+    pub atmosphere_iterations: f32,
+    pub ocean_advection: f32,
+    pub atmosphere_advection: f32,
+    pub precipitation_iterations: f32,
+    /// The temperature land starts at before heat is carried over it.
+    pub land_temperature: f32,
+    /// How much of the difference between the equator and the poles land
+    /// starts with, compared to the sea.
+    pub land_variation: f32,
+    /// How quickly air loses its moisture over land, divided by the number
+    /// of points to give the fraction lost over each cell.
+    pub rain_decay: f32,
+    /// How much moisture air picks up over each ocean cell.
+    pub evaporation: f32,
+    /// How freely currents flow over the continental shelf compared to the
+    /// open ocean.
+    pub shelf_weight: f32,
+}
+
+/// How the year-round weather varies over the seasons.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClimateConfig {
+    /// The tilt of the planet's axis in degrees, which sets how far the sun
+    /// moves north and south over the year.
+    pub axial_tilt: f32,
+    /// How strongly temperatures over land follow the sun.
+    pub land_seasonality: f32,
+    /// How strongly temperatures over the sea follow the sun. The sea's
+    /// heat capacity keeps this smaller than over land.
+    pub ocean_seasonality: f32,
+    /// How much of the year's rain follows the rain belts as they move with
+    /// the sun, from 0 for rain spread evenly over the year.
+    pub monsoon: f32,
+}
+
+/// The scales used to report heights, temperatures and rainfall in
+/// physical units. See [`crate::units`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitsConfig {
+    /// The planet's radius in metres.
+    pub radius: f32,
+    /// How many times taller the terrain is generated than its real height,
+    /// so relief stays visible on a whole planet.
+    pub exaggeration: f32,
+    /// The height, as a fraction of the planet's radius, reported as 0 m.
+    /// Land is never generated below 0, so by default the coast sits at sea
+    /// level.
+    pub sea_level: f32,
+    /// The temperature in °C of a cell with a dimensionless temperature of
+    /// 0, roughly midway between the equator and the poles.
+    pub mean_temperature: f32,
+    /// The change in °C from a dimensionless temperature of 0 to 1, so the
+    /// equator is about this much warmer than `mean_temperature`.
+    pub temperature_range: f32,
+    /// The rainfall in mm per year of a cell with a precipitation of 1.
+    pub water_budget: f32,
+}
+
+impl PlanetConfig {
+    /// Reads a preset from a RON file. Any parameter missing from the file
+    /// keeps its default value.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let config: Self = ron::from_str(&text).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, e)
+        })?;
+        if config.tectonics.num_plates == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "tectonics.num_plates must be at least 1",
+            ));
+        }
+        Ok(config)
+    }
+
+    /// Writes every parameter to a RON file that [`PlanetConfig::load`] can
+    /// read back.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let text = ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, e)
+            })?;
+        fs::write(path, text)
+    }
+}
+
+impl Default for TectonicsConfig {
+    fn default() -> Self {
+        Self {
+            num_plates: 20,
+            continental_fraction: 0.3,
+            oceanic_poles: false,
+            ocean_height: -0.03,
+            island_height: -0.01,
+            trench_depth: 1.0,
+            max_height: 0.09,
+            continent_height: 0.01,
+            continent_slope: 0.05,
+            continental_noise: NoiseConfig {
+                octaves: 5,
+                warp: 0.2,
+                ..NoiseConfig::default()
+            },
+            oceanic_noise: NoiseConfig {
+                octaves: 2,
+                ..NoiseConfig::default()
+            },
+            convergent_stress: 0.2,
+            divergent_stress: 0.05,
+            ocean_stress: 0.4,
+            transform_ratio: 1.0,
+            steps: 0,
+            step_distance: 0.5,
+            collision_uplift: 0.005,
+            subduction_uplift: 0.002,
+            rift_height: -0.02,
+            subsidence: 0.1,
+            hotspots: 3,
+            hotspot_height: 0.05,
+            hotspot_flank: 0.3,
+            chain_length: 8,
+            chain_spacing: 1.5,
+            chain_decay: 0.8,
+        }
+    }
+}
+
+impl Default for NoiseConfig {
+    fn default() -> Self {
+        Self {
+            kind: NoiseKind::Fbm,
+            frequency: 1.0,
+            amplitude: 0.02,
+            octaves: 1,
+            lacunarity: 2.0,
+            persistence: 0.5,
+            warp: 0.0,
+        }
+    }
+}
+
+impl Default for TerrainConfig {
+    fn default() -> Self {
+        Self {
+            mountain_height: 0.03,
+            highland_height: 0.02,
+            hills_height: 0.01,
+            land_height: -0.01,
+            shelf_height: -0.02,
+            slope_height: -0.025,
+            trench_height: -0.045,
+        }
+    }
+}
+
+impl Default for ErosionConfig {
+    fn default() -> Self {
+        Self {
+            hydraulic_iterations: 5,
+            capacity: 0.002,
+            erosion_rate: 0.3,
+            deposition_rate: 0.3,
+            delta_height: -0.01,
+            thermal_iterations: 10,
+            talus: 0.4,
+            thermal_rate: 0.5,
+        }
+    }
+}
+
+impl Default for HydrologyConfig {
+    fn default() -> Self {
+        Self {
+            river_threshold: 0.0005,
+            lake_depth: 0.001,
+            lake_evaporation: 1.0,
+        }
+    }
+}
+
+impl Default for BiomeConfig {
+    fn default() -> Self {
+        Self {
+            table: vec![
+                BiomeRule::new(Biome::Snow, -0.6, 2.0),
+                BiomeRule::new(Biome::Tundra, -0.35, 2.0),
+                BiomeRule::new(Biome::Desert, 2.0, 0.1),
+                BiomeRule::new(Biome::Taiga, -0.1, 2.0),
+                BiomeRule::new(Biome::Steppe, 0.3, 0.2),
+                BiomeRule::new(Biome::Shrubland, 0.3, 0.3),
+                BiomeRule::new(Biome::Grassland, 0.3, 0.4),
+                BiomeRule::new(Biome::Forest, 0.3, 0.7),
+                BiomeRule::new(Biome::TemperateRainforest, 0.3, 2.0),
+                BiomeRule::new(Biome::Savanna, 2.0, 0.6),
+                BiomeRule::new(Biome::Rainforest, 2.0, 2.0),
+            ],
+            snowline_temperature: -0.3,
+            alpine_temperature: -0.1,
+            wetland_flow: 0.0015,
+            mangrove_temperature: 0.3,
+            mangrove_precipitation: 0.6,
+            coral_temperature: 0.4,
+            sea_ice_temperature: -0.65,
+            pack_ice_temperature: -0.75,
+        }
+    }
+}
+
+impl BiomeRule {
+    pub const fn new(biome: Biome, max_temperature: f32, max_precipitation: f32) -> Self {
+        Self {
+            biome,
+            max_temperature,
+            max_precipitation,
+        }
+    }
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            ocean_iterations: 10.0,
+            atmosphere_iterations: 1.0,
+            ocean_advection: 0.3,
+            atmosphere_advection: 0.2,
+            precipitation_iterations: 0.5,
+            land_temperature: 0.2,
+            land_variation: 0.5,
+            rain_decay: 500.0,
+            evaporation: 0.3,
+            shelf_weight: 0.3,
+        }
+    }
+}
+
+impl Default for ClimateConfig {
+    fn default() -> Self {
+        Self {
+            axial_tilt: 23.4,
+            land_seasonality: 1.0,
+            ocean_seasonality: 0.3,
+            monsoon: 0.8,
+        }
+    }
+}
+
+impl Default for UnitsConfig {
+    fn default() -> Self {
+        Self {
+            radius: 6_371_000.0,
+            exaggeration: 64.0,
+            sea_level: 0.0,
+            mean_temperature: 12.0,
+            temperature_range: 28.0,
+            water_budget: 3000.0,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,240 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bevy::math::Vec3;
+use serde_json::{Value, json};
+use triangulation::Triangulation;
+
+use crate::Planet;
+use crate::export::{OCEAN_RADIUS, normals};
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+const GLB_MAGIC: u32 = 0x46546C67;
+const GLB_VERSION: u32 = 2;
+const JSON_CHUNK: u32 = 0x4E4F534A;
+const BIN_CHUNK: u32 = 0x004E4942;
+
+const OCEAN_SUBDIVISIONS: usize = 3;
+const OCEAN_COLOR: [f32; 4] = [0.0, 0.0, 0.75, 0.25];
+
+/// What to include when exporting a planet to glTF.
+#[derive(Clone, Debug)]
+pub struct GltfOptions {
+    /// Adds the translucent ocean sphere as a second mesh.
+    pub ocean: bool,
+    /// Multiplies every position, the planet having radius 1.
+    pub scale: f32,
+}
+
+impl Default for GltfOptions {
+    fn default() -> Self {
+        Self {
+            ocean: true,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Planet {
+    /// Writes the planet surface to a `.gltf` file, with its buffer in a
+    /// `.bin` file alongside it.
+    ///
+    /// Vertices carry biome colors in `COLOR_0` along with the custom
+    /// attributes `_HEIGHT` (the surface height in metres), `_TEMPERATURE`
+    /// and `_PRECIPITATION`.
+    pub fn export_gltf(&self, path: impl AsRef<Path>, options: &GltfOptions) -> io::Result<()> {
+        let path = path.as_ref();
+        let bin_path = path.with_extension("bin");
+        let uri = bin_path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("planet.bin");
+
+        let (mut gltf, buffer) = self.gltf(options);
+        gltf["buffers"][0]["uri"] = json!(uri);
+
+        fs::write(&bin_path, buffer)?;
+        fs::write(path, serde_json::to_vec_pretty(&gltf)?)
+    }
+
+    /// Writes the same scene as [`Planet::export_gltf`] to a single binary
+    /// `.glb` file.
+    pub fn export_glb(&self, path: impl AsRef<Path>, options: &GltfOptions) -> io::Result<()> {
+        let (gltf, mut buffer) = self.gltf(options);
+
+        let mut json = serde_json::to_vec(&gltf)?;
+        pad(&mut json, b' ');
+        pad(&mut buffer, 0);
+
+        let length = 12 + 8 + json.len() + 8 + buffer.len();
+        let mut glb = Vec::with_capacity(length);
+        glb.extend(GLB_MAGIC.to_le_bytes());
+        glb.extend(GLB_VERSION.to_le_bytes());
+        glb.extend((length as u32).to_le_bytes());
+        glb.extend((json.len() as u32).to_le_bytes());
+        glb.extend(JSON_CHUNK.to_le_bytes());
+        glb.extend(json);
+        glb.extend((buffer.len() as u32).to_le_bytes());
+        glb.extend(BIN_CHUNK.to_le_bytes());
+        glb.extend(buffer);
+
+        fs::write(path, glb)
+    }
+
+    fn gltf(&self, options: &GltfOptions) -> (Value, Vec<u8>) {
+        let mut builder = Builder::default();
+
+        let positions: Vec<Vec3> = self.points.iter().map(|p| options.scale * *p).collect();
+        let normals = normals(&self.points, &self.triangles);
+        let colors: Vec<Vec3> = self.biomes.iter().map(|b| Vec3::from(b.color())).collect();
+
+        let planet = json!({
+            "name": "planet",
+            "primitives": [{
+                "attributes": {
+                    "POSITION": builder.vec3s(&positions, true),
+                    "NORMAL": builder.vec3s(&normals, false),
+                    "COLOR_0": builder.vec3s(&colors, false),
+                    "_HEIGHT": builder.floats(&self.surface_height),
+                    "_TEMPERATURE": builder.floats(&self.atmospheric_temperatures),
+                    "_PRECIPITATION": builder.floats(&self.precipitation),
+                },
+                "indices": builder.indices(&self.triangles),
+                "material": 0,
+            }],
+        });
+        let mut meshes = vec![planet];
+        let mut nodes = vec![json!({ "name": "planet", "mesh": 0 })];
+
+        if options.ocean {
+            let ocean = Triangulation::sphere(options.scale * OCEAN_RADIUS, OCEAN_SUBDIVISIONS);
+            let normals: Vec<Vec3> = ocean.points.iter().map(|p| p.normalize()).collect();
+            meshes.push(json!({
+                "name": "ocean",
+                "primitives": [{
+                    "attributes": {
+                        "POSITION": builder.vec3s(&ocean.points, true),
+                        "NORMAL": builder.vec3s(&normals, false),
+                    },
+                    "indices": builder.indices(&ocean.triangles),
+                    "material": 1,
+                }],
+            }));
+            nodes.push(json!({ "name": "ocean", "mesh": 1 }));
+        }
+
+        let gltf = json!({
+            "asset": { "version": "2.0", "generator": "planet" },
+            "scene": 0,
+            "scenes": [{ "nodes": (0..nodes.len()).collect::<Vec<usize>>() }],
+            "nodes": nodes,
+            "meshes": meshes,
+            "materials": [
+                {
+                    "name": "surface",
+                    "pbrMetallicRoughness": {
+                        "baseColorFactor": [1.0, 1.0, 1.0, 1.0],
+                        "metallicFactor": 0.0,
+                        "roughnessFactor": 1.0,
+                    },
+                },
+                {
+                    "name": "ocean",
+                    "pbrMetallicRoughness": {
+                        "baseColorFactor": OCEAN_COLOR,
+                        "metallicFactor": 0.0,
+                        "roughnessFactor": 0.2,
+                    },
+                    "alphaMode": "BLEND",
+                },
+            ],
+            "buffers": [{ "byteLength": builder.buffer.len() }],
+            "bufferViews": builder.buffer_views,
+            "accessors": builder.accessors,
+        });
+
+        return (gltf, builder.buffer);
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+}
+
+impl Builder {
+    fn vec3s(&mut self, values: &Vec<Vec3>, bounds: bool) -> usize {
+        let view = self.view(
+            values.iter().flat_map(|v| v.to_array()).flat_map(f32::to_le_bytes),
+            ARRAY_BUFFER,
+        );
+        let mut accessor = json!({
+            "bufferView": view,
+            "componentType": FLOAT,
+            "count": values.len(),
+            "type": "VEC3",
+        });
+        if bounds {
+            let min = values.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+            let max = values.iter().copied().fold(Vec3::NEG_INFINITY, Vec3::max);
+            accessor["min"] = json!(min.to_array());
+            accessor["max"] = json!(max.to_array());
+        }
+        self.accessor(accessor)
+    }
+
+    fn floats(&mut self, values: &Vec<f32>) -> usize {
+        let view = self.view(
+            values.iter().flat_map(|v| v.to_le_bytes()),
+            ARRAY_BUFFER,
+        );
+        self.accessor(json!({
+            "bufferView": view,
+            "componentType": FLOAT,
+            "count": values.len(),
+            "type": "SCALAR",
+        }))
+    }
+
+    fn indices(&mut self, values: &Vec<usize>) -> usize {
+        let view = self.view(
+            values.iter().flat_map(|v| (*v as u32).to_le_bytes()),
+            ELEMENT_ARRAY_BUFFER,
+        );
+        self.accessor(json!({
+            "bufferView": view,
+            "componentType": UNSIGNED_INT,
+            "count": values.len(),
+            "type": "SCALAR",
+        }))
+    }
+
+    fn view(&mut self, bytes: impl Iterator<Item = u8>, target: u32) -> usize {
+        let offset = self.buffer.len();
+        self.buffer.extend(bytes);
+        self.buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": self.buffer.len() - offset,
+            "target": target,
+        }));
+        self.buffer_views.len() - 1
+    }
+
+    fn accessor(&mut self, accessor: Value) -> usize {
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+}
+
+fn pad(bytes: &mut Vec<u8>, value: u8) {
+    while !bytes.len().is_multiple_of(4) {
+        bytes.push(value);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use bevy::math::Vec3;
+
+pub use gltf::GltfOptions;
+
+pub mod gltf;
+mod obj;
+mod ply;
+
+/// The radius of the ocean sphere drawn beneath the planet surface.
+pub const OCEAN_RADIUS: f32 = 0.995;
+
+pub fn normals(points: &Vec<Vec3>, triangles: &Vec<usize>) -> Vec<Vec3> {
+    let mut normals = vec![Vec3::ZERO].repeat(points.len());
+    for t in triangles.chunks(3) {
+        let a = points[t[0]];
+        let b = points[t[1]];
+        let c = points[t[2]];
+        let normal = (b - a).cross(c - a);
+        for p in t {
+            normals[*p] += normal;
+        }
+    }
+    normals.iter().map(|normal| normal.normalize_or_zero()).collect()
+}
\ No newline at end of file
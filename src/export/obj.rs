@@ -0,0 +1,60 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::{Biome, Planet};
+use crate::export::normals;
+
+impl Planet {
+    /// Writes the planet surface to a Wavefront `.obj` file, with one
+    /// material per biome in a `.mtl` file alongside it.
+    ///
+    /// Each triangle takes the biome shared by most of its corners.
+    pub fn export_obj(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mtl_path = path.with_extension("mtl");
+        let mtl_name = mtl_path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("planet.mtl");
+
+        let mut groups: Vec<(Biome, Vec<&[usize]>)> = vec![];
+        for t in self.triangles.chunks(3) {
+            let biome = majority(self.biomes[t[0]], self.biomes[t[1]], self.biomes[t[2]]);
+            match groups.iter_mut().find(|(b, _)| *b == biome) {
+                Some((_, triangles)) => triangles.push(t),
+                None => groups.push((biome, vec![t])),
+            }
+        }
+
+        let mut mtl = BufWriter::new(File::create(&mtl_path)?);
+        for (biome, _) in &groups {
+            let [r, g, b] = biome.color();
+            writeln!(mtl, "newmtl {biome:?}")?;
+            writeln!(mtl, "Kd {r} {g} {b}")?;
+            writeln!(mtl)?;
+        }
+        mtl.flush()?;
+
+        let mut obj = BufWriter::new(File::create(path)?);
+        writeln!(obj, "mtllib {mtl_name}")?;
+        writeln!(obj, "o planet")?;
+        for point in &self.points {
+            writeln!(obj, "v {} {} {}", point.x, point.y, point.z)?;
+        }
+        for normal in normals(&self.points, &self.triangles) {
+            writeln!(obj, "vn {} {} {}", normal.x, normal.y, normal.z)?;
+        }
+        for (biome, triangles) in &groups {
+            writeln!(obj, "usemtl {biome:?}")?;
+            for t in triangles {
+                let [a, b, c] = [t[0] + 1, t[1] + 1, t[2] + 1];
+                writeln!(obj, "f {a}//{a} {b}//{b} {c}//{c}")?;
+            }
+        }
+        obj.flush()
+    }
+}
+
+fn majority(a: Biome, b: Biome, c: Biome) -> Biome {
+    if b == c { b } else { a }
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::{Biome, Planet, Terrain};
+
+impl Planet {
+    /// Writes the planet surface to an ASCII `.ply` file with every per-cell
+    /// value as a vertex property.
+    ///
+    /// `terrain` and `biome` are the fixed codes from [`Terrain::code`]
+    /// and [`Biome::code`], which the header also lists by name.
+    pub fn export_ply(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut ply = BufWriter::new(File::create(path)?);
+
+        writeln!(ply, "ply")?;
+        writeln!(ply, "format ascii 1.0")?;
+        writeln!(ply, "comment seed {}", self.seed)?;
+        for terrain in Terrain::ALL {
+            writeln!(ply, "comment terrain {} {terrain:?}", terrain.code())?;
+        }
+        for biome in Biome::ALL {
+            writeln!(ply, "comment biome {} {biome:?}", biome.code())?;
+        }
+        writeln!(ply, "element vertex {}", self.points.len())?;
+        for property in [
+            "float x", "float y", "float z",
+            "uchar red", "uchar green", "uchar blue",
+            "uchar terrain", "uchar biome",
+            "float ocean_temperature",
+            "float atmospheric_temperature",
+            "float precipitation",
+            "float ocean_current_x", "float ocean_current_y", "float ocean_current_z",
+            "float atmospheric_current_x", "float atmospheric_current_y", "float atmospheric_current_z",
+        ] {
+            writeln!(ply, "property {property}")?;
+        }
+        writeln!(ply, "element face {}", self.triangles.len() / 3)?;
+        writeln!(ply, "property list uchar uint vertex_indices")?;
+        writeln!(ply, "end_header")?;
+
+        for p in 0..self.points.len() {
+            let point = self.points[p];
+            let [r, g, b] = self.biomes[p].color().map(|c| (255.0 * c.clamp(0.0, 1.0)) as u8);
+            let ocean_current = self.ocean_currents[p];
+            let atmospheric_current = self.atmospheric_currents[p];
+            writeln!(
+                ply,
+                "{} {} {} {r} {g} {b} {} {} {} {} {} {} {} {} {} {} {}",
+                point.x, point.y, point.z,
+                self.terrain[p].code(),
+                self.biomes[p].code(),
+                self.ocean_temperatures[p],
+                self.atmospheric_temperatures[p],
+                self.precipitation[p],
+                ocean_current.x, ocean_current.y, ocean_current.z,
+                atmospheric_current.x, atmospheric_current.y, atmospheric_current.z,
+            )?;
+        }
+        for t in self.triangles.chunks(3) {
+            writeln!(ply, "3 {} {} {}", t[0], t[1], t[2])?;
+        }
+        ply.flush()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::HydrologyConfig;
+
+/// Water pooled in a depression on land, up to the height where it would
+/// spill over.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Lake {
+    pub cells: Vec<usize>,
+    /// The lake cell all its water drains through.
+    pub outlet: usize,
+    /// The height of the water's surface.
+    pub level: f32,
+    /// Whether evaporation removes all the water flowing in, so the lake
+    /// never overflows to the sea and turns salty.
+    pub endorheic: bool,
+}
+
+/// Finds the lakes left by filling depressions, grouping neighbouring cells
+/// filled deeper than `config.lake_depth`.
+///
+/// Each lake's cells are rerouted to drain through the lake to its outlet,
+/// the cell where the most water leaves it. A lake whose inflow is less than
+/// `evaporation` from each of its cells is endorheic, and its outlet is cut
+/// off so nothing drains out of it.
+pub fn lakes(
+    adjacencies: &Vec<Vec<usize>>,
+    heights: &Vec<f32>,
+    filled: &Vec<f32>,
+    flow: &Vec<f32>,
+    downstream: &mut Vec<Option<usize>>,
+    evaporation: f32,
+    config: &HydrologyConfig,
+) -> Vec<Lake> {
+    let flooded: Vec<bool> = (0..heights.len()).map(|p| {
+        filled[p] - heights[p] > config.lake_depth
+    }).collect();
+
+    let mut visited = vec![false].repeat(heights.len());
+    let mut lakes = vec![];
+    for start in 0..heights.len() {
+        if !flooded[start] || visited[start] { continue }
+
+        visited[start] = true;
+        let mut cells = vec![];
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            cells.push(p);
+            for q in &adjacencies[p] {
+                if flooded[*q] && !visited[*q] {
+                    visited[*q] = true;
+                    stack.push(*q);
+                }
+            }
+        }
+        cells.sort();
+
+        let in_lake = |p: usize| cells.binary_search(&p).is_ok();
+        let exits: Vec<usize> = cells.iter().copied().filter(|p| {
+            downstream[*p].is_none_or(|q| !in_lake(q))
+        }).collect();
+        let inflow: f32 = exits.iter().map(|p| flow[*p]).sum();
+        let outlet = *exits.iter()
+            .max_by(|a, b| flow[**a].total_cmp(&flow[**b]))
+            .unwrap();
+
+        let mut rerouted = vec![outlet];
+        let mut i = 0;
+        while i < rerouted.len() {
+            let p = rerouted[i];
+            for q in &adjacencies[p] {
+                if in_lake(*q) && !rerouted.contains(q) {
+                    downstream[*q] = Some(p);
+                    rerouted.push(*q);
+                }
+            }
+            i += 1;
+        }
+
+        let endorheic = inflow < evaporation * cells.len() as f32;
+        if endorheic {
+            downstream[outlet] = None;
+        }
+        let level = cells.iter().map(|p| filled[*p]).fold(f32::NEG_INFINITY, f32::max);
+
+        lakes.push(Lake {
+            outlet,
+            level,
+            endorheic,
+            cells,
+        });
+    }
+
+    return lakes;
+}
\ No newline at end of file
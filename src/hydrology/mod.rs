@@ -0,0 +1,265 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::config::HydrologyConfig;
+use crate::terrain::Terrain;
+
+pub use lakes::Lake;
+use lakes::lakes;
+
+mod lakes;
+
+/// The smallest drop given to every step across filled depressions, so
+/// water always has a direction to flow.
+const FILL_SLOPE: f32 = 1e-6;
+
+pub struct Hydrology {
+    /// The neighbour each land cell drains into.
+    pub downstream: Vec<Option<usize>>,
+    /// The precipitation draining through each land cell.
+    pub flow: Vec<f32>,
+    /// The drainage basin of each land cell, numbered by river mouth.
+    pub basins: Vec<Option<usize>>,
+    /// Rivers as lists of cells from source to mouth or confluence.
+    pub rivers: Vec<Vec<usize>>,
+    pub lakes: Vec<Lake>,
+}
+
+/// Routes water from every land cell to the sea.
+///
+/// Depressions are filled with a priority flood outward from the coast,
+/// which also gives each land cell the neighbour it was reached from as the
+/// cell it drains into. Precipitation is then accumulated downstream, and
+/// cells whose flow exceeds the river threshold are traced into rivers.
+///
+/// Filled depressions become lakes, each draining through a single outlet.
+/// A lake whose catchment is too small to outpace its evaporation never
+/// overflows, so its outlet is cut off and its basin drains nowhere.
+pub fn hydrology(
+    adjacencies: &Vec<Vec<usize>>,
+    terrain: &Vec<Terrain>,
+    heights: &Vec<f32>,
+    precipitation: &Vec<f32>,
+    config: &HydrologyConfig,
+) -> Hydrology {
+    let num_points = heights.len();
+    let land = |p: usize| terrain[p].is_land();
+
+    let mut filled = heights.clone();
+    let mut downstream = vec![None; num_points];
+    let mut visited = vec![false].repeat(num_points);
+    let mut queue = BinaryHeap::new();
+    for p in 0..num_points {
+        if !land(p) {
+            visited[p] = true;
+            queue.push(Lowest(heights[p], p));
+        }
+    }
+    if queue.is_empty()
+        && let Some(p) = (0..num_points).min_by(|a, b| heights[*a].total_cmp(&heights[*b]))
+    {
+        visited[p] = true;
+        queue.push(Lowest(heights[p], p));
+    }
+
+    let mut order = vec![];
+    while let Some(Lowest(height, p)) = queue.pop() {
+        if land(p) {
+            order.push(p);
+        }
+        for q in &adjacencies[p] {
+            let q = *q;
+            if visited[q] { continue }
+            visited[q] = true;
+            filled[q] = filled[q].max(height + FILL_SLOPE);
+            downstream[q] = Some(p);
+            queue.push(Lowest(filled[q], q));
+        }
+    }
+
+    let flow = accumulate(&order, &downstream, &land, precipitation);
+    let land_cells = order.len().max(1) as f32;
+    let rainfall = order.iter().map(|p| precipitation[*p]).sum::<f32>() / land_cells;
+    let evaporation = config.lake_evaporation * rainfall;
+    let lakes = lakes(adjacencies, heights, &filled, &flow, &mut downstream, evaporation, config);
+    let order = drainage_order(&downstream, &land);
+    let flow = accumulate(&order, &downstream, &land, precipitation);
+
+    let mut basins = vec![None; num_points];
+    let mut num_basins = 0;
+    for p in &order {
+        basins[*p] = match downstream[*p] {
+            Some(q) if land(q) => basins[q],
+            _ => {
+                num_basins += 1;
+                Some(num_basins - 1)
+            },
+        };
+    }
+
+    let threshold = config.river_threshold * num_points as f32;
+    let is_river = |p: usize| land(p) && flow[p] > threshold;
+    let mut has_tributary = vec![false].repeat(num_points);
+    for (p, q) in downstream.iter().enumerate() {
+        if let Some(q) = q
+            && is_river(p)
+        {
+            has_tributary[*q] = true;
+        }
+    }
+
+    let mut in_river = vec![false].repeat(num_points);
+    let mut rivers = vec![];
+    let mut sources: Vec<usize> = (0..num_points).filter(|p| {
+        is_river(*p) && !has_tributary[*p]
+    }).collect();
+    sources.sort_by(|a, b| flow[*b].total_cmp(&flow[*a]));
+    for source in sources {
+        let mut river = vec![source];
+        in_river[source] = true;
+        let mut p = source;
+        while let Some(q) = downstream[p] {
+            river.push(q);
+            if !land(q) || in_river[q] { break }
+            in_river[q] = true;
+            p = q;
+        }
+        rivers.push(river);
+    }
+
+    return Hydrology {
+        downstream,
+        flow,
+        basins,
+        rivers,
+        lakes,
+    };
+}
+
+/// Orders the land cells so each comes after the cell it drains into,
+/// starting from the cells that drain into the sea or nowhere.
+fn drainage_order(
+    downstream: &Vec<Option<usize>>,
+    land: &impl Fn(usize) -> bool,
+) -> Vec<usize> {
+    let mut upstream = vec![vec![]; downstream.len()];
+    let mut order = vec![];
+    for (p, q) in downstream.iter().enumerate() {
+        if !land(p) { continue }
+        match q {
+            Some(q) if land(*q) => upstream[*q].push(p),
+            _ => order.push(p),
+        }
+    }
+
+    let mut i = 0;
+    while i < order.len() {
+        let p = order[i];
+        order.extend_from_slice(&upstream[p]);
+        i += 1;
+    }
+    return order;
+}
+
+/// Sums the precipitation over every land cell upstream of each cell,
+/// visiting cells from the source downwards.
+fn accumulate(
+    order: &Vec<usize>,
+    downstream: &Vec<Option<usize>>,
+    land: &impl Fn(usize) -> bool,
+    precipitation: &Vec<f32>,
+) -> Vec<f32> {
+    let mut flow: Vec<f32> = (0..downstream.len()).map(|p| {
+        if land(p) { precipitation[p] } else { 0.0 }
+    }).collect();
+    for p in order.iter().rev() {
+        if let Some(q) = downstream[*p]
+            && land(q)
+        {
+            flow[q] += flow[*p];
+        }
+    }
+    return flow;
+}
+
+/// A cell in the priority flood, ordered so the lowest is popped first.
+struct Lowest(f32, usize);
+
+impl PartialEq for Lowest {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Lowest {}
+
+impl PartialOrd for Lowest {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Lowest {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Follows the drainage from `p` to the cell it ends at.
+    fn mouth(downstream: &Vec<Option<usize>>, mut p: usize) -> usize {
+        while let Some(q) = downstream[p] {
+            p = q;
+        }
+        p
+    }
+
+    #[test]
+    fn wet_lake_drains_and_dry_basin_is_closed() {
+        // A coast (1) behind the sea (0) with two valleys. Cells 3 and 4 are
+        // a depression fed by the rain on 3-6. Cells 8 and 9 are a depression
+        // in a rainless valley that spills over both 7 and 12.
+        let edges = [
+            (0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6),
+            (1, 7), (7, 8), (8, 9), (9, 12), (12, 1),
+        ];
+        let heights = vec![
+            -0.1, 0.01, 0.05, 0.02, 0.02, 0.08, 0.09, 0.05, 0.02, 0.02, 0.0, 0.0, 0.05,
+        ];
+        let precipitation = vec![
+            0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        ];
+        let mut adjacencies = vec![vec![]; heights.len()];
+        for (p, q) in edges {
+            adjacencies[p].push(q);
+            adjacencies[q].push(p);
+        }
+        let mut terrain = vec![Terrain::Land; heights.len()];
+        terrain[0] = Terrain::Ocean;
+
+        let hydrology = hydrology(
+            &adjacencies,
+            &terrain,
+            &heights,
+            &precipitation,
+            &HydrologyConfig::default(),
+        );
+
+        let lakes = &hydrology.lakes;
+        assert_eq!(lakes.len(), 2);
+        assert_eq!(lakes[0].cells, vec![3, 4]);
+        assert!(!lakes[0].endorheic);
+        assert_eq!(lakes[1].cells, vec![8, 9]);
+        assert!(lakes[1].endorheic);
+
+        for p in [3, 4] {
+            assert_eq!(mouth(&hydrology.downstream, p), 0);
+        }
+        for p in [8, 9] {
+            assert_eq!(mouth(&hydrology.downstream, p), lakes[1].outlet);
+        }
+    }
+}
@@ -1,45 +1,126 @@
 use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
+pub use crate::climate::{ClimateZone, MONTHS};
+pub use crate::config::PlanetConfig;
+pub use crate::hydrology::Lake;
+pub use crate::terrain::Terrain;
+pub use crate::terrain::biomes::Biome;
+pub use crate::terrain::boundaries::{BoundaryKind, BoundarySegment};
+pub use crate::terrain::hotspots::Volcano;
+pub use crate::terrain::tectonics::Plate;
+
+use crate::climate::climate;
+use crate::hydrology::hydrology;
 use crate::template::template;
-use crate::terrain::biomes::{Biome, biomes};
+use crate::terrain::biomes::{BiomeInputs, biomes};
+use crate::terrain::boundaries::boundaries;
+use crate::terrain::erosion::{hydraulic_erosion, thermal_erosion};
 use crate::terrain::tectonics::tectonics;
 use crate::terrain::borders::borders;
-use crate::terrain::{terrain, Terrain};
+use crate::terrain::terrain;
 use crate::weather::weather;
 
+mod climate;
+pub mod config;
+pub mod export;
+mod hydrology;
+pub mod map;
+mod random;
 mod render;
+mod save;
 mod template;
 mod terrain;
+pub mod units;
 mod weather;
 
-#[derive(Resource)]
+#[derive(Resource, Serialize, Deserialize)]
 pub struct Planet {
+    pub seed: u64,
+    pub config: PlanetConfig,
     pub points: Vec<Vec3>,
+    pub triangles: Vec<usize>,
     pub adjacencies: Vec<Vec<usize>>,
+    #[serde(skip, default = "render::empty_mesh")]
     pub mesh: Mesh,
     pub terrain: Vec<Terrain>,
     pub biomes: Vec<Biome>,
+    /// The height of the ground in each cell, in metres above sea level as
+    /// set by `config.units`. Lake beds keep their depth here.
+    pub elevation: Vec<f32>,
+    /// The height each cell is drawn at, in metres above sea level: lakes
+    /// are levelled to their surface, ice sheets flattened and
+    /// rainforest canopies smoothed.
+    pub surface_height: Vec<f32>,
     pub borders: Vec<Vec<Vec3>>,
+    pub plates: Vec<Plate>,
+    pub plate_ids: Vec<usize>,
+    pub plate_velocities: Vec<Vec3>,
+    pub stress: Vec<f32>,
+    pub boundaries: Vec<BoundarySegment>,
+    pub volcanoes: Vec<Volcano>,
     pub ocean_currents: Vec<Vec3>,
     pub ocean_temperatures: Vec<f32>,
     pub atmospheric_currents: Vec<Vec3>,
     pub atmospheric_temperatures: Vec<f32>,
     pub precipitation: Vec<f32>,
+    /// The mean temperature of each month in °C, starting from January.
+    pub monthly_temperatures: Vec<[f32; MONTHS]>,
+    /// The rainfall of each month in mm, starting from January.
+    pub monthly_rainfall: Vec<[f32; MONTHS]>,
+    /// The Köppen–Geiger zone of each cell not covered by the sea.
+    pub climate_zone: Vec<Option<ClimateZone>>,
+    /// The neighbour each land cell drains into.
+    pub downstream: Vec<Option<usize>>,
+    /// The precipitation draining through each land cell.
+    pub flow: Vec<f32>,
+    /// The drainage basin of each land cell, numbered by river mouth.
+    pub basins: Vec<Option<usize>>,
+    /// Rivers as lists of cells from source to mouth or confluence.
+    pub rivers: Vec<Vec<usize>>,
+    pub lakes: Vec<Lake>,
 }
 
 impl Planet {
-    pub fn new(num_points: usize) -> Planet {
+    pub fn new(num_points: usize, config: PlanetConfig) -> Planet {
+        Self::from_seed(rand::random(), num_points, config)
+    }
+
+    /// Generates a planet entirely from `seed`, so the same seed, point
+    /// count and config always produce an identical planet.
+    pub fn from_seed(seed: u64, num_points: usize, config: PlanetConfig) -> Planet {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
         let (
             mut template, 
             edges, 
             adjacencies
-        ) = template(num_points);
+        ) = template(num_points, &mut rng);
 
-        let mut heights = tectonics(
+        let tectonics = tectonics(
             &template.points, 
             &adjacencies, 
+            &config.tectonics,
+            &mut rng,
         );
-        let terrain = terrain(&mut heights);
+        let boundaries = boundaries(
+            &template,
+            &edges,
+            &tectonics.plates,
+            &tectonics.plate_ids,
+            &tectonics.velocities,
+            &config.tectonics,
+        );
+        let mut heights = tectonics.heights;
+        thermal_erosion(
+            &template.points,
+            &adjacencies,
+            &mut heights,
+            &config.erosion,
+        );
+        let terrain = terrain(&mut heights, &config.terrain);
 
         let (
             ocean_currents, 
@@ -53,24 +134,71 @@ impl Planet {
             &terrain,
             &edges, 
             &adjacencies,
+            &config.weather,
         );
 
+        // The weather keeps the coastline it was simulated with, even if
+        // sediment builds new land.
+        hydraulic_erosion(
+            &template.points,
+            &adjacencies,
+            &terrain,
+            &mut heights,
+            &precipitation,
+            &config.erosion,
+        );
+        let mut terrain = self::terrain(&mut heights, &config.terrain);
+
+        let hydrology = hydrology(
+            &adjacencies,
+            &terrain,
+            &heights,
+            &precipitation,
+            &config.hydrology,
+        );
+        let elevation = heights.iter().map(|h| config.units.metres(*h)).collect();
+        for lake in &hydrology.lakes {
+            for p in &lake.cells {
+                heights[*p] = lake.level;
+                terrain[*p] = if lake.endorheic {
+                    Terrain::SaltLake
+                } else {
+                    Terrain::Lake
+                };
+            }
+        }
+
         let biomes = biomes(
-            &template.points, 
+            &BiomeInputs {
+                points: &template.points,
+                adjacencies: &adjacencies,
+                terrain: &terrain,
+                ocean_temperatures: &ocean_temperatures,
+                atmospheric_temperatures: &atmospheric_temperatures,
+                precipitation: &precipitation,
+                flow: &hydrology.flow,
+            },
+            &config.biomes,
+        );
+        let climate = climate(
+            &template.points,
             &terrain,
             &ocean_temperatures,
             &atmospheric_temperatures,
             &precipitation,
+            &config.climate,
+            &config.units,
         );
 
         for p in 0..template.points.len() {
             match biomes[p] {
-                Biome::Ice => heights[p] = 0.0,
+                Biome::SeaIce | Biome::PackIce => heights[p] = 0.0,
                 Biome::Rainforest => heights[p] *= 0.5,
                 _ => {},
             }
             template.points[p] *= 1.0 + heights[p]
         }
+        let surface_height = heights.iter().map(|h| config.units.metres(*h)).collect();
 
         let borders = borders(
             &template, 
@@ -79,17 +207,36 @@ impl Planet {
         let mesh = template.mesh();
 
         return Planet { 
+            seed,
+            config,
             points: template.points, 
+            triangles: template.triangles,
             adjacencies, 
             mesh, 
             terrain, 
             biomes,
+            elevation,
+            surface_height,
             borders,
+            plates: tectonics.plates,
+            plate_ids: tectonics.plate_ids,
+            plate_velocities: tectonics.velocities,
+            stress: tectonics.stress,
+            boundaries,
+            volcanoes: tectonics.volcanoes,
             ocean_currents,
             ocean_temperatures,
             atmospheric_currents,
             atmospheric_temperatures,
             precipitation,
+            monthly_temperatures: climate.temperatures,
+            monthly_rainfall: climate.rainfall,
+            climate_zone: climate.zones,
+            downstream: hydrology.downstream,
+            flow: hydrology.flow,
+            basins: hydrology.basins,
+            rivers: hydrology.rivers,
+            lakes: hydrology.lakes,
         };
     }
 }
\ No newline at end of file
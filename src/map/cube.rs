@@ -0,0 +1,92 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bevy::math::Vec3;
+
+use crate::Planet;
+use crate::map::{Interpolation, Layer, Projection, save_png};
+
+/// One face of a cube map, using the OpenGL face orientations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CubeFace {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+    PositiveZ,
+    NegativeZ,
+}
+
+impl CubeFace {
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::PositiveX,
+        CubeFace::NegativeX,
+        CubeFace::PositiveY,
+        CubeFace::NegativeY,
+        CubeFace::PositiveZ,
+        CubeFace::NegativeZ,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::PositiveX => "px",
+            Self::NegativeX => "nx",
+            Self::PositiveY => "py",
+            Self::NegativeY => "ny",
+            Self::PositiveZ => "pz",
+            Self::NegativeZ => "nz",
+        }
+    }
+}
+
+impl Projection for CubeFace {
+    fn aspect(&self) -> f32 {
+        1.0
+    }
+
+    fn direction(&self, u: f32, v: f32) -> Option<Vec3> {
+        let s = 2.0 * u - 1.0;
+        let t = 2.0 * v - 1.0;
+        let direction = match self {
+            Self::PositiveX => Vec3::new(1.0, -t, -s),
+            Self::NegativeX => Vec3::new(-1.0, -t, s),
+            Self::PositiveY => Vec3::new(s, 1.0, t),
+            Self::NegativeY => Vec3::new(s, -1.0, -t),
+            Self::PositiveZ => Vec3::new(s, -t, 1.0),
+            Self::NegativeZ => Vec3::new(-s, -t, -1.0),
+        };
+        Some(direction.normalize())
+    }
+}
+
+impl Planet {
+    /// Bakes every [`Layer`], a 16-bit height map and an object-space normal
+    /// map into six `resolution` square faces, written to `directory` as
+    /// `<layer>_<face>.png`.
+    pub fn save_cube_maps(
+        &self,
+        directory: impl AsRef<Path>,
+        resolution: u32,
+        interpolation: Interpolation,
+        normal_strength: f32,
+    ) -> io::Result<()> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+        for face in CubeFace::ALL {
+            for layer in Layer::ALL {
+                let path = directory.join(format!("{}_{}.png", layer.name(), face.name()));
+                save_png(&self.map(layer, &face, resolution, interpolation), &path)?;
+            }
+
+            let path = directory.join(format!("height_{}.png", face.name()));
+            self.height_map(&face, resolution, interpolation)
+                .save(path)
+                .map_err(io::Error::other)?;
+
+            let path = directory.join(format!("normal_{}.png", face.name()));
+            save_png(&self.normal_map(&face, resolution, interpolation, normal_strength), &path)?;
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::math::Vec3;
+
+use crate::map::{Projection, direction};
+
+/// Latitude and longitude mapped linearly onto a 2:1 image.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Equirectangular;
+
+impl Projection for Equirectangular {
+    fn aspect(&self) -> f32 {
+        0.5
+    }
+
+    fn direction(&self, u: f32, v: f32) -> Option<Vec3> {
+        let longitude = TAU * u - PI;
+        let latitude = PI / 2.0 - PI * v;
+        Some(direction(latitude, longitude))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::math::{Vec2, Vec3};
+
+use crate::map::{Projection, direction};
+
+const HEIGHT: f32 = 0.866_025_4;
+const NET_WIDTH: f32 = 5.5;
+const NET_HEIGHT: f32 = 3.0 * HEIGHT;
+
+/// The sphere projected gnomonically onto an icosahedron, which is then
+/// unfolded into a strip of 20 triangles.
+///
+/// Each face is nearly equal in area and shape, so distortion stays small
+/// everywhere, including at the poles.
+#[derive(Clone, Debug)]
+pub struct Icosahedral {
+    faces: Vec<([Vec2; 3], [Vec3; 3])>,
+}
+
+impl Icosahedral {
+    pub fn new() -> Self {
+        let ring_latitude = 0.5f32.atan();
+        let north = Vec3::Y;
+        let south = Vec3::NEG_Y;
+        let upper = |i: usize| direction(ring_latitude, TAU * i as f32 / 5.0 - PI);
+        let lower = |i: usize| direction(-ring_latitude, TAU * (i as f32 + 0.5) / 5.0 - PI);
+
+        let mut faces = vec![];
+        for i in 0..5 {
+            let x = i as f32;
+            faces.push((
+                [Vec2::new(x + 0.5, 0.0), Vec2::new(x, HEIGHT), Vec2::new(x + 1.0, HEIGHT)],
+                [north, upper(i), upper(i + 1)],
+            ));
+            faces.push((
+                [Vec2::new(x, HEIGHT), Vec2::new(x + 0.5, 2.0 * HEIGHT), Vec2::new(x + 1.0, HEIGHT)],
+                [upper(i), lower(i), upper(i + 1)],
+            ));
+            faces.push((
+                [Vec2::new(x + 1.0, HEIGHT), Vec2::new(x + 0.5, 2.0 * HEIGHT), Vec2::new(x + 1.5, 2.0 * HEIGHT)],
+                [upper(i + 1), lower(i), lower(i + 1)],
+            ));
+            faces.push((
+                [Vec2::new(x + 0.5, 2.0 * HEIGHT), Vec2::new(x + 1.0, 3.0 * HEIGHT), Vec2::new(x + 1.5, 2.0 * HEIGHT)],
+                [lower(i), south, lower(i + 1)],
+            ));
+        }
+
+        Self { faces }
+    }
+}
+
+impl Default for Icosahedral {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Projection for Icosahedral {
+    fn aspect(&self) -> f32 {
+        NET_HEIGHT / NET_WIDTH
+    }
+
+    fn direction(&self, u: f32, v: f32) -> Option<Vec3> {
+        let point = Vec2::new(NET_WIDTH * u, NET_HEIGHT * v);
+        for (net, sphere) in &self.faces {
+            let [a, b, c] = *net;
+            let area = (b - a).perp_dot(c - a);
+            let weights = [
+                (c - b).perp_dot(point - b) / area,
+                (a - c).perp_dot(point - c) / area,
+                (b - a).perp_dot(point - a) / area,
+            ];
+            if weights.iter().all(|w| *w >= 0.0) {
+                let direction: Vec3 = (0..3).map(|i| weights[i] * sphere[i]).sum();
+                return Some(direction.normalize());
+            }
+        }
+        None
+    }
+}
\ No newline at end of file
@@ -0,0 +1,248 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bevy::color::{ColorToPacked, LinearRgba, Srgba};
+use bevy::math::Vec3;
+use image::{ImageBuffer, Luma, Rgb, RgbImage};
+
+use crate::Planet;
+
+/// The elevation, as a fraction of the planet's radius, mapped to the full
+/// range of a height map.
+const HEIGHT_RANGE: f32 = 0.1;
+
+pub use cube::CubeFace;
+pub use equirectangular::Equirectangular;
+pub use icosahedral::Icosahedral;
+pub use mollweide::Mollweide;
+pub use orthographic::Orthographic;
+pub use sampler::{Interpolation, Sample, Sampler};
+
+mod cube;
+mod equirectangular;
+mod icosahedral;
+mod mollweide;
+mod orthographic;
+mod sampler;
+
+/// Maps points on a flat image back onto the sphere.
+pub trait Projection {
+    /// The height of the image as a fraction of its width.
+    fn aspect(&self) -> f32;
+
+    /// The unit vector shown at `(u, v)`, both running from 0 to 1 with `v`
+    /// pointing down the image, or `None` if the point lies outside the map.
+    fn direction(&self, u: f32, v: f32) -> Option<Vec3>;
+}
+
+/// A per-cell quantity that can be drawn on a map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layer {
+    Biomes,
+    Elevation,
+    OceanTemperature,
+    AtmosphericTemperature,
+    Precipitation,
+    /// Köppen–Geiger zones, with the sea left in its biome colors.
+    ClimateZones,
+}
+
+impl Layer {
+    pub const ALL: [Layer; 6] = [
+        Layer::Biomes,
+        Layer::Elevation,
+        Layer::OceanTemperature,
+        Layer::AtmosphericTemperature,
+        Layer::Precipitation,
+        Layer::ClimateZones,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Biomes => "biomes",
+            Self::Elevation => "elevation",
+            Self::OceanTemperature => "ocean_temperature",
+            Self::AtmosphericTemperature => "atmospheric_temperature",
+            Self::Precipitation => "precipitation",
+            Self::ClimateZones => "climate_zones",
+        }
+    }
+}
+
+impl Planet {
+    /// The elevation of the ground in every cell above sea level, as a
+    /// fraction of the planet's radius. Unlike the mesh, this is not
+    /// flattened under ice or smoothed under rainforest.
+    fn heights(&self) -> Vec<f32> {
+        self.elevation.iter().map(|e| self.config.units.relief(*e)).collect()
+    }
+
+    /// The linear color of `layer` at `sample`.
+    fn layer_color(&self, layer: Layer, sample: &Sample, heights: &Vec<f32>) -> [f32; 3] {
+        match layer {
+            Layer::Biomes => self.biomes[sample.cell()].color(),
+            Layer::Elevation => elevation_color(sample.value(heights)),
+            Layer::OceanTemperature => temperature_color(sample.value(&self.ocean_temperatures)),
+            Layer::AtmosphericTemperature => temperature_color(sample.value(&self.atmospheric_temperatures)),
+            Layer::Precipitation => precipitation_color(sample.value(&self.precipitation)),
+            Layer::ClimateZones => match self.climate_zone[sample.cell()] {
+                Some(zone) => zone.color(),
+                None => self.biomes[sample.cell()].color(),
+            },
+        }
+    }
+
+    /// Draws `layer` as a `width` pixel wide map, leaving black any pixel
+    /// outside the projection.
+    pub fn map(
+        &self,
+        layer: Layer,
+        projection: &impl Projection,
+        width: u32,
+        interpolation: Interpolation,
+    ) -> RgbImage {
+        let height = ((width as f32 * projection.aspect()).round() as u32).max(1);
+        let heights = self.heights();
+        let mut sampler = Sampler::new(self);
+        RgbImage::from_fn(width, height, |x, y| {
+            let u = (x as f32 + 0.5) / width as f32;
+            let v = (y as f32 + 0.5) / height as f32;
+            match projection.direction(u, v) {
+                Some(direction) => {
+                    let sample = sampler.sample(direction, interpolation);
+                    srgb(self.layer_color(layer, &sample, &heights))
+                },
+                None => Rgb([0, 0, 0]),
+            }
+        })
+    }
+
+    /// Draws the elevation as a 16-bit grayscale map, with sea level at
+    /// half intensity and [`HEIGHT_RANGE`] above and below it at the extremes.
+    pub fn height_map(
+        &self,
+        projection: &impl Projection,
+        width: u32,
+        interpolation: Interpolation,
+    ) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+        let height = ((width as f32 * projection.aspect()).round() as u32).max(1);
+        let heights = self.heights();
+        let mut sampler = Sampler::new(self);
+        ImageBuffer::from_fn(width, height, |x, y| {
+            let u = (x as f32 + 0.5) / width as f32;
+            let v = (y as f32 + 0.5) / height as f32;
+            let value = match projection.direction(u, v) {
+                Some(direction) => sampler.sample(direction, interpolation).value(&heights),
+                None => 0.0,
+            };
+            let value = (0.5 + 0.5 * value / HEIGHT_RANGE).clamp(0.0, 1.0);
+            Luma([(u16::MAX as f32 * value) as u16])
+        })
+    }
+
+    /// Draws the surface normal of the displaced planet in object space,
+    /// encoded as `0.5 + 0.5 * normal`. Heights are multiplied by `strength`
+    /// first, since the relief is otherwise too small to see.
+    pub fn normal_map(
+        &self,
+        projection: &impl Projection,
+        width: u32,
+        interpolation: Interpolation,
+        strength: f32,
+    ) -> RgbImage {
+        let height = ((width as f32 * projection.aspect()).round() as u32).max(1);
+        let heights = self.heights();
+        let mut sampler = Sampler::new(self);
+        let mut surface = |u: f32, v: f32| {
+            projection.direction(u, v).map(|direction| {
+                let elevation = sampler.sample(direction, interpolation).value(&heights);
+                (1.0 + strength * elevation) * direction
+            })
+        };
+
+        let du = 1.0 / width as f32;
+        let dv = 1.0 / height as f32;
+        RgbImage::from_fn(width, height, |x, y| {
+            let u = (x as f32 + 0.5) * du;
+            let v = (y as f32 + 0.5) * dv;
+            let Some(centre) = surface(u, v) else {
+                return Rgb([0, 0, 0]);
+            };
+
+            let left = surface(u - du, v).unwrap_or(centre);
+            let right = surface(u + du, v).unwrap_or(centre);
+            let up = surface(u, v - dv).unwrap_or(centre);
+            let down = surface(u, v + dv).unwrap_or(centre);
+
+            let mut normal = (right - left).cross(down - up).normalize_or(centre.normalize());
+            if normal.dot(centre) < 0.0 {
+                normal = -normal;
+            }
+            let color = normal.to_array().map(|n| (255.0 * (0.5 + 0.5 * n)) as u8);
+            Rgb(color)
+        })
+    }
+
+    /// Writes a map of every [`Layer`] into `directory`.
+    pub fn save_maps(
+        &self,
+        directory: impl AsRef<Path>,
+        projection: &impl Projection,
+        width: u32,
+        interpolation: Interpolation,
+    ) -> io::Result<()> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+        for layer in Layer::ALL {
+            let path = directory.join(format!("{}.png", layer.name()));
+            save_png(&self.map(layer, projection, width, interpolation), &path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The unit vector at a latitude and longitude, with the poles on the y axis.
+pub fn direction(latitude: f32, longitude: f32) -> Vec3 {
+    Vec3::new(
+        latitude.cos() * longitude.cos(),
+        latitude.sin(),
+        -latitude.cos() * longitude.sin(),
+    )
+}
+
+pub fn save_png(image: &RgbImage, path: &Path) -> io::Result<()> {
+    image.save(path).map_err(io::Error::other)
+}
+
+fn srgb(color: [f32; 3]) -> Rgb<u8> {
+    let [r, g, b] = color;
+    Rgb(Srgba::from(LinearRgba::rgb(r, g, b)).to_u8_array_no_alpha())
+}
+
+fn elevation_color(height: f32) -> [f32; 3] {
+    if height < 0.0 {
+        let depth = (-height / 0.05).min(1.0);
+        lerp([0.1, 0.3, 0.6], [0.0, 0.02, 0.15], depth)
+    } else if height < 0.03 {
+        lerp([0.1, 0.35, 0.05], [0.45, 0.35, 0.15], height / 0.03)
+    } else {
+        lerp([0.45, 0.35, 0.15], [1.0, 1.0, 1.0], ((height - 0.03) / 0.06).min(1.0))
+    }
+}
+
+fn temperature_color(temperature: f32) -> [f32; 3] {
+    if temperature < 0.0 {
+        lerp([1.0, 1.0, 1.0], [0.0, 0.05, 0.8], (-temperature).min(1.0))
+    } else {
+        lerp([1.0, 1.0, 1.0], [0.8, 0.05, 0.0], temperature.min(1.0))
+    }
+}
+
+fn precipitation_color(precipitation: f32) -> [f32; 3] {
+    lerp([0.8, 0.65, 0.4], [0.0, 0.2, 0.7], precipitation.clamp(0.0, 1.0))
+}
+
+fn lerp(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [0, 1, 2].map(|i| a[i] + t * (b[i] - a[i]))
+}
\ No newline at end of file
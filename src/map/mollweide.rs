@@ -0,0 +1,29 @@
+use std::f32::consts::PI;
+
+use bevy::math::Vec3;
+
+use crate::map::{Projection, direction};
+
+/// An equal-area projection of the whole sphere onto a 2:1 ellipse, so polar
+/// regions keep their true size.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mollweide;
+
+impl Projection for Mollweide {
+    fn aspect(&self) -> f32 {
+        0.5
+    }
+
+    fn direction(&self, u: f32, v: f32) -> Option<Vec3> {
+        let x = 2.0 * u - 1.0;
+        let y = 1.0 - 2.0 * v;
+        if x*x + y*y > 1.0 {
+            return None;
+        }
+
+        let theta = y.asin();
+        let latitude = ((2.0 * theta + (2.0 * theta).sin()) / PI).clamp(-1.0, 1.0).asin();
+        let longitude = PI * x / theta.cos().max(f32::EPSILON);
+        Some(direction(latitude, longitude))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+use bevy::math::Vec3;
+
+use crate::map::{Projection, direction};
+
+/// The hemisphere seen from infinitely far away above a point, as in a
+/// photograph of the planet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Orthographic {
+    /// The latitude at the centre of the view, in radians.
+    pub latitude: f32,
+    /// The longitude at the centre of the view, in radians.
+    pub longitude: f32,
+}
+
+impl Projection for Orthographic {
+    fn aspect(&self) -> f32 {
+        1.0
+    }
+
+    fn direction(&self, u: f32, v: f32) -> Option<Vec3> {
+        let x = 2.0 * u - 1.0;
+        let y = 1.0 - 2.0 * v;
+        let z = 1.0 - x*x - y*y;
+        if z < 0.0 {
+            return None;
+        }
+
+        let (sin_latitude, cos_latitude) = self.latitude.sin_cos();
+        let (sin_longitude, cos_longitude) = self.longitude.sin_cos();
+        let forward = direction(self.latitude, self.longitude);
+        let east = Vec3::new(-sin_longitude, 0.0, -cos_longitude);
+        let north = Vec3::new(
+            -sin_latitude * cos_longitude,
+            cos_latitude,
+            sin_latitude * sin_longitude,
+        );
+        Some(x * east + y * north + z.sqrt() * forward)
+    }
+}
\ No newline at end of file
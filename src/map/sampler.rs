@@ -0,0 +1,115 @@
+use bevy::math::Vec3;
+
+use crate::Planet;
+use crate::template::nearest;
+
+/// Up to three cells and their weights describing a point on the sphere.
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub cells: [usize; 3],
+    pub weights: [f32; 3],
+}
+
+/// How a map interpolates between the cells of the triangulation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Every pixel takes the value of its nearest cell.
+    #[default]
+    Nearest,
+    /// Every pixel blends the three corners of the triangle containing it.
+    Barycentric,
+}
+
+/// Finds the cells nearest to directions on the sphere by walking the
+/// triangulation, so consecutive nearby queries are cheap.
+pub struct Sampler<'a> {
+    planet: &'a Planet,
+    directions: Vec<Vec3>,
+    triangles: Vec<Vec<usize>>,
+    hint: usize,
+}
+
+impl<'a> Sampler<'a> {
+    pub fn new(planet: &'a Planet) -> Self {
+        let directions = planet.points.iter().map(|p| p.normalize()).collect();
+
+        let mut triangles = vec![vec![]; planet.points.len()];
+        for t in 0..planet.triangles.len() / 3 {
+            for p in &planet.triangles[3*t..3*t+3] {
+                triangles[*p].push(t);
+            }
+        }
+
+        Self {
+            planet,
+            directions,
+            triangles,
+            hint: 0,
+        }
+    }
+
+    pub fn sample(&mut self, direction: Vec3, interpolation: Interpolation) -> Sample {
+        let p = self.nearest(direction);
+        match interpolation {
+            Interpolation::Nearest => Sample {
+                cells: [p; 3],
+                weights: [1.0, 0.0, 0.0],
+            },
+            Interpolation::Barycentric => self.barycentric(p, direction),
+        }
+    }
+
+    /// The cell nearest to `direction`, walking from the last one found.
+    pub fn nearest(&mut self, direction: Vec3) -> usize {
+        self.hint = nearest(&self.directions, &self.planet.adjacencies, direction, self.hint);
+        return self.hint;
+    }
+
+    fn barycentric(&self, p: usize, direction: Vec3) -> Sample {
+        let mut best = Sample {
+            cells: [p; 3],
+            weights: [1.0, 0.0, 0.0],
+        };
+        let mut best_weight = f32::NEG_INFINITY;
+
+        for t in &self.triangles[p] {
+            let cells = [
+                self.planet.triangles[3*t],
+                self.planet.triangles[3*t+1],
+                self.planet.triangles[3*t+2],
+            ];
+            let [a, b, c] = cells.map(|q| self.directions[q]);
+            let weights = [
+                direction.dot(b.cross(c)),
+                direction.dot(c.cross(a)),
+                direction.dot(a.cross(b)),
+            ];
+            let total: f32 = weights.iter().sum();
+            if total == 0.0 { continue }
+
+            let weights = weights.map(|w| w / total);
+            let min_weight = weights.iter().copied().fold(f32::INFINITY, f32::min);
+            if min_weight > best_weight {
+                best = Sample { cells, weights };
+                best_weight = min_weight;
+            }
+            if min_weight >= 0.0 { break }
+        }
+
+        return best;
+    }
+}
+
+impl Sample {
+    pub fn value(&self, values: &Vec<f32>) -> f32 {
+        (0..3).map(|i| self.weights[i] * values[self.cells[i]]).sum()
+    }
+
+    /// The cell with the largest weight.
+    pub fn cell(&self) -> usize {
+        let i = (0..3)
+            .max_by(|a, b| self.weights[*a].total_cmp(&self.weights[*b]))
+            .unwrap();
+        self.cells[i]
+    }
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use bevy::math::Vec3;
+use rand::Rng;
+
+pub fn random_unit(rng: &mut impl Rng) -> Vec3 {
+    let y: f32 = rng.random_range(-1.0..1.0);
+    let angle: f32 = rng.random_range(0.0..std::f32::consts::TAU);
+    let radius = (1.0 - y*y).sqrt();
+    Vec3::new(radius * angle.cos(), y, radius * angle.sin())
+}
+
+pub fn random_units(rng: &mut impl Rng, n: usize) -> Vec<Vec3> {
+    (0..n).map(|_| {
+        random_unit(rng)
+    }).collect()
+}
\ No newline at end of file
@@ -1,6 +1,21 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::prelude::*;
+#[cfg(feature = "viewer")]
 use triangulation::Triangulation;
 
+pub fn empty_mesh() -> Mesh {
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+}
+
+pub fn mesh(points: &Vec<Vec3>, triangles: &Vec<usize>) -> Mesh {
+    let indices = triangles.iter().map(|t| *t as u32).collect();
+    empty_mesh()
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, points.clone())
+        .with_inserted_indices(Indices::U32(indices))
+}
+
+#[cfg(feature = "viewer")]
 impl crate::Planet {
     pub fn render(
         &mut self,
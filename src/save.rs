@@ -0,0 +1,93 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Planet;
+use crate::render::mesh;
+
+const MAGIC: &[u8; 4] = b"PLNT";
+/// The layout of [`Planet`] and its config in saved files. Bump it for any
+/// change to that layout once a version has been released.
+const VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct SaveRef<'a> {
+    version: u32,
+    planet: &'a Planet,
+}
+
+impl Planet {
+    /// Writes every field except the mesh to a compact binary file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        bincode::serialize_into(&mut writer, self).map_err(invalid_data)?;
+        writer.flush()
+    }
+
+    /// Reads a planet written by [`Planet::save`] and rebuilds its mesh.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Planet> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a planet file"));
+        }
+
+        let mut version = [0; 4];
+        reader.read_exact(&mut version)?;
+        check_version(u32::from_le_bytes(version))?;
+
+        let planet = bincode::deserialize_from(reader).map_err(invalid_data)?;
+        Ok(with_mesh(planet))
+    }
+
+    /// Writes the same data as [`Planet::save`] as human-readable JSON.
+    pub fn save_json(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        let save = SaveRef { version: VERSION, planet: self };
+        serde_json::to_writer(writer, &save).map_err(invalid_data)
+    }
+
+    /// Reads a planet written by [`Planet::save_json`] and rebuilds its mesh.
+    ///
+    /// The version is checked before the planet is read, so a file from
+    /// another version is rejected as such rather than by a missing field.
+    pub fn load_json(path: impl AsRef<Path>) -> io::Result<Planet> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut save: Value = serde_json::from_reader(reader).map_err(invalid_data)?;
+        let version = save["version"]
+            .as_u64()
+            .ok_or_else(|| invalid_data("missing planet file version"))?;
+        check_version(u32::try_from(version).unwrap_or(u32::MAX))?;
+
+        let planet = serde_json::from_value(save["planet"].take()).map_err(invalid_data)?;
+        Ok(with_mesh(planet))
+    }
+}
+
+fn with_mesh(mut planet: Planet) -> Planet {
+    planet.mesh = mesh(&planet.points, &planet.triangles);
+    planet
+}
+
+fn check_version(version: u32) -> io::Result<()> {
+    if version == VERSION {
+        Ok(())
+    } else {
+        Err(invalid_data(format!(
+            "unsupported planet file version {version}, expected {VERSION}"
+        )))
+    }
+}
+
+fn invalid_data(
+    error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use bevy::math::{DVec3, Vec3};
+use triangulation::Triangulation;
+
+/// Triangulates points on the unit sphere as their convex hull, which on a
+/// sphere is also their Delaunay triangulation.
+///
+/// Starting from a tetrahedron, the points are added one at a time in the
+/// order given. Every point waiting to be added remembers one triangle it
+/// can see; adding a point removes all the triangles it can see and joins
+/// the edge of the hole to it, and the points that saw a removed triangle
+/// look for another among the new ones. Triangles wind counter-clockwise
+/// seen from outside, and `half_edges` pairs each edge with the same edge
+/// in the neighbouring triangle.
+pub fn hull(points: Vec<Vec3>) -> Triangulation {
+    let directions: Vec<DVec3> = points.iter().map(|p| p.as_dvec3()).collect();
+    let mut hull = Hull::new(&directions);
+    for p in 0..directions.len() {
+        if hull.corners.contains(&p) { continue }
+        hull.insert(p);
+    }
+
+    let mut index = vec![usize::MAX; hull.faces.len()];
+    let mut triangles = vec![];
+    for (f, face) in hull.faces.iter().enumerate() {
+        if hull.removed[f] { continue }
+        index[f] = triangles.len() / 3;
+        triangles.extend(face);
+    }
+    let half_edges = (0..hull.faces.len())
+        .filter(|f| !hull.removed[*f])
+        .flat_map(|f| hull.twins[f])
+        .map(|(g, i)| 3*index[g] + i)
+        .collect();
+
+    Triangulation {
+        points,
+        triangles,
+        half_edges,
+    }
+}
+
+struct Hull<'a> {
+    directions: &'a Vec<DVec3>,
+    /// The points of the starting tetrahedron.
+    corners: [usize; 4],
+    faces: Vec<[usize; 3]>,
+    /// For each edge of each face, the face and edge on its other side.
+    twins: Vec<[(usize, usize); 3]>,
+    removed: Vec<bool>,
+    /// The points waiting to be added that see each face.
+    outside: Vec<Vec<usize>>,
+    /// The face each point waiting to be added sees.
+    seen: Vec<usize>,
+}
+
+impl<'a> Hull<'a> {
+    /// Starts from a tetrahedron of the first four points not lying in a
+    /// plane.
+    fn new(directions: &'a Vec<DVec3>) -> Self {
+        let n = directions.len();
+        let a = 0;
+        let b = (1..n)
+            .find(|p| directions[*p] != directions[a])
+            .expect("a sphere needs at least four distinct points");
+        let c = (b + 1..n)
+            .find(|p| {
+                (directions[b] - directions[a]).cross(directions[*p] - directions[a]) != DVec3::ZERO
+            })
+            .expect("a sphere needs at least four points not in a line");
+        let d = (c + 1..n)
+            .find(|p| volume(directions, [a, b, c], *p) != 0.0)
+            .expect("a sphere needs at least four points not in a plane");
+        let (b, c) = if volume(directions, [a, b, c], d) > 0.0 { (c, b) } else { (b, c) };
+
+        let faces = vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]];
+        let twins = faces.iter().map(|face| {
+            [0, 1, 2].map(|i| {
+                let (from, to) = (face[i], face[(i + 1) % 3]);
+                (0..4)
+                    .flat_map(|g| (0..3).map(move |j| (g, j)))
+                    .find(|(g, j)| faces[*g][*j] == to && faces[*g][(*j + 1) % 3] == from)
+                    .unwrap()
+            })
+        }).collect();
+
+        let mut hull = Self {
+            directions,
+            corners: [a, b, c, d],
+            faces,
+            twins,
+            removed: vec![false; 4],
+            outside: vec![vec![]; 4],
+            seen: vec![usize::MAX; n],
+        };
+        for p in 0..n {
+            if hull.corners.contains(&p) { continue }
+            hull.find_seen(p, 0..4);
+        }
+        return hull;
+    }
+
+    /// Adds point `p`, replacing every face it can see with a fan of faces
+    /// from the edge of the hole to `p`.
+    fn insert(&mut self, p: usize) {
+        let mut visible = vec![self.seen[p]];
+        self.removed[self.seen[p]] = true;
+        let mut horizon = vec![];
+        let mut i = 0;
+        while i < visible.len() {
+            let f = visible[i];
+            for edge in 0..3 {
+                let (g, j) = self.twins[f][edge];
+                if self.removed[g] { continue }
+                if volume(self.directions, self.faces[g], p) > 0.0 {
+                    self.removed[g] = true;
+                    visible.push(g);
+                } else {
+                    horizon.push((g, j));
+                }
+            }
+            i += 1;
+        }
+
+        let first = self.faces.len();
+        let mut starting_at = HashMap::new();
+        for (k, (g, j)) in horizon.iter().enumerate() {
+            let to = self.faces[*g][*j];
+            let from = self.faces[*g][(*j + 1) % 3];
+            self.faces.push([from, to, p]);
+            self.twins.push([(*g, *j), (0, 0), (0, 0)]);
+            self.removed.push(false);
+            self.outside.push(vec![]);
+            self.twins[*g][*j] = (first + k, 0);
+            starting_at.insert(from, first + k);
+        }
+        for f in first..self.faces.len() {
+            let next = starting_at[&self.faces[f][1]];
+            self.twins[f][1] = (next, 2);
+            self.twins[next][2] = (f, 1);
+        }
+
+        for f in visible {
+            for q in std::mem::take(&mut self.outside[f]) {
+                if q != p {
+                    self.find_seen(q, first..self.faces.len());
+                }
+            }
+        }
+    }
+
+    /// Finds a face among `candidates` that point `p` can see, or failing
+    /// that any face left on the hull. A point that sees none, which only
+    /// rounding can cause on a sphere, takes the face it is nearest to
+    /// seeing.
+    fn find_seen(&mut self, p: usize, candidates: std::ops::Range<usize>) {
+        let best = |faces: &mut dyn Iterator<Item = usize>| {
+            faces
+                .map(|f| (f, volume(self.directions, self.faces[f], p)))
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap()
+        };
+        let (mut f, volume) = best(&mut candidates.into_iter());
+        if volume <= 0.0 {
+            f = best(&mut (0..self.faces.len()).filter(|f| !self.removed[*f])).0;
+        }
+        self.seen[p] = f;
+        self.outside[f].push(p);
+    }
+}
+
+/// Six times the signed volume of the tetrahedron from `face` to point `p`,
+/// positive when `p` lies outside the face.
+fn volume(directions: &Vec<DVec3>, face: [usize; 3], p: usize) -> f64 {
+    let [a, b, c] = face.map(|q| directions[q]);
+    (b - a).cross(c - a).dot(directions[p] - a)
+}
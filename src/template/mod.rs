@@ -1,14 +1,54 @@
+use bevy::math::Vec3;
+use rand::Rng;
 use triangulation::Triangulation;
 
+use crate::random::random_units;
 use edges::edges;
 use adjacencies::adjacencies;
+use hull::hull;
 
 mod adjacencies;
 mod edges;
+mod hull;
 
-pub fn template(num_points: usize) -> (Triangulation, Vec<Vec<usize>>, Vec<Vec<usize>>) {
-    let template = Triangulation::random_sphere(1.0, num_points);
+pub fn template(
+    num_points: usize, 
+    rng: &mut impl Rng,
+) -> (Triangulation, Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let points = random_units(rng, num_points);
+    let template = hull(points);
     let edges = edges(&template);
     let adjacencies = adjacencies(&template, &edges);
     return (template, edges, adjacencies);
+}
+
+/// The typical angle between neighbouring cells among `num_points` spread
+/// evenly over the unit sphere.
+pub fn spacing(num_points: usize) -> f32 {
+    (4.0 * std::f32::consts::PI / num_points as f32).sqrt()
+}
+
+/// Greedily walks from `start` towards `direction`, which on a Delaunay
+/// triangulation of unit `points` always ends at the nearest cell. Starting
+/// near the answer keeps the walk short.
+pub fn nearest(
+    points: &Vec<Vec3>,
+    adjacencies: &Vec<Vec<usize>>,
+    direction: Vec3,
+    start: usize,
+) -> usize {
+    let mut p = start;
+    let mut closest = points[p].dot(direction);
+    loop {
+        let next = adjacencies[p].iter()
+            .map(|q| (*q, points[*q].dot(direction)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match next {
+            Some((q, dot)) if dot > closest => {
+                p = q;
+                closest = dot;
+            },
+            _ => return p,
+        }
+    }
 }
\ No newline at end of file
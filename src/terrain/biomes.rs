@@ -1,115 +1,199 @@
 use bevy::math::Vec3;
+use serde::{Deserialize, Serialize};
 
+use crate::config::{BiomeConfig, BiomeRule};
 use crate::terrain::Terrain;
-use crate::weather::temperatures::temperature;
+use crate::weather::temperatures::{land_temperature, sea_temperature};
 
-const ICE_TEMPERATURE: f32 = -0.7;
-const SNOW_TEMPERATURE: f32 = -0.6;
-const TUNDRA_TEMPERATURE: f32 = -0.2;
-
-const ARID_PRECIPITATION: f32 = 0.1;
-
-const TROPICAL_TEMPERATURE: f32 = 0.3;
-const RAINFOREST_PRECIPITATION: f32 = 0.6;
+impl Biome {
+    pub const ALL: [Biome; 22] = [
+        Self::Mountain,
+        Self::Alpine,
+        Self::Forest,
+        Self::Grassland,
+        Self::Savanna,
+        Self::Rainforest,
+        Self::TemperateRainforest,
+        Self::Taiga,
+        Self::Shrubland,
+        Self::Steppe,
+        Self::Desert,
+        Self::Tundra,
+        Self::Wetland,
+        Self::Mangrove,
+        Self::Lake,
+        Self::SaltLake,
+        Self::CoralReef,
+        Self::Shallow,
+        Self::Ocean,
+        Self::SeaIce,
+        Self::PackIce,
+        Self::Snow,
+    ];
 
-const FOREST_PRECIPITATION: f32 = 0.4;
+    /// A fixed number for the biome in exported files. Codes are never
+    /// renumbered or reused, so a new variant takes the next free code.
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::Mountain => 0,
+            Self::Alpine => 1,
+            Self::Forest => 2,
+            Self::Grassland => 3,
+            Self::Savanna => 4,
+            Self::Rainforest => 5,
+            Self::TemperateRainforest => 6,
+            Self::Taiga => 7,
+            Self::Shrubland => 8,
+            Self::Steppe => 9,
+            Self::Desert => 10,
+            Self::Tundra => 11,
+            Self::Wetland => 12,
+            Self::Mangrove => 13,
+            Self::Lake => 14,
+            Self::SaltLake => 15,
+            Self::CoralReef => 16,
+            Self::Shallow => 17,
+            Self::Ocean => 18,
+            Self::SeaIce => 19,
+            Self::PackIce => 20,
+            Self::Snow => 21,
+        }
+    }
 
-impl Biome {
     pub const fn color(&self) -> [f32; 3] {
         match self {
             Self::Mountain => [0.2, 0.2, 0.2],
+            Self::Alpine => [0.4, 0.45, 0.3],
             Self::Forest => [0.0, 0.3, 0.0],
             Self::Grassland => [0.1, 0.4, 0.0],
-            Self::Plains => [0.3, 0.4, 0.05],
+            Self::Savanna => [0.5, 0.45, 0.15],
             Self::Rainforest => [0.0, 0.2, 0.0],
+            Self::TemperateRainforest => [0.0, 0.25, 0.1],
+            Self::Taiga => [0.05, 0.2, 0.1],
+            Self::Shrubland => [0.4, 0.4, 0.15],
+            Self::Steppe => [0.5, 0.5, 0.3],
             Self::Desert => [0.7, 0.7, 0.4],
             Self::Tundra => [0.1, 0.3, 0.1],
+            Self::Wetland => [0.2, 0.35, 0.25],
+            Self::Mangrove => [0.1, 0.3, 0.15],
+            Self::Lake => [0.05, 0.25, 0.4],
+            Self::SaltLake => [0.85, 0.85, 0.8],
+            Self::CoralReef => [0.3, 0.7, 0.7],
             Self::Shallow => [0.5, 0.5, 0.3],
             Self::Ocean => [0.0, 0.1, 0.1],
-            Self::Ice => [0.5, 1.0, 1.0],
+            Self::SeaIce => [0.7, 0.9, 0.95],
+            Self::PackIce => [0.5, 1.0, 1.0],
             Self::Snow => [1.0, 1.0, 1.0],
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Biome {
+    /// Bare rock.
     Mountain,
+    /// Meadows above the tree line.
+    Alpine,
     Forest,
     Grassland,
-    Plains,
+    Savanna,
     Rainforest,
+    TemperateRainforest,
+    /// Boreal forest.
+    Taiga,
+    Shrubland,
+    Steppe,
     Desert,
     Tundra,
+    /// Floodplains along large rivers.
+    Wetland,
+    /// Wet tropical coasts.
+    Mangrove,
+    Lake,
+    SaltLake,
+    CoralReef,
     Shallow,
     Ocean,
-    Ice,
+    /// Sea that freezes over in winter.
+    SeaIce,
+    /// Sea that stays frozen all year.
+    PackIce,
     Snow,
 }
 
-pub fn biomes(
-    points: &Vec<Vec3>,
-    terrain: &Vec<Terrain>,
-    ocean_temperatures: &Vec<f32>,
-    atmospheric_temperatures: &Vec<f32>,
-    precipitation: &Vec<f32>,
-) -> Vec<Biome> {
+/// What the planet looks like by the time biomes are picked.
+pub struct BiomeInputs<'a> {
+    pub points: &'a Vec<Vec3>,
+    pub adjacencies: &'a Vec<Vec<usize>>,
+    pub terrain: &'a Vec<Terrain>,
+    pub ocean_temperatures: &'a Vec<f32>,
+    pub atmospheric_temperatures: &'a Vec<f32>,
+    pub precipitation: &'a Vec<f32>,
+    /// The precipitation draining through each land cell.
+    pub flow: &'a Vec<f32>,
+}
+
+/// Picks each cell's biome. Land is looked up in the Whittaker table in
+/// `config`, after checking for the biomes set by terrain, drainage or the
+/// coast; water is picked by its temperature and depth.
+pub fn biomes(inputs: &BiomeInputs, config: &BiomeConfig) -> Vec<Biome> {
+    let BiomeInputs {
+        points,
+        adjacencies,
+        terrain,
+        ocean_temperatures,
+        atmospheric_temperatures,
+        precipitation,
+        flow,
+    } = inputs;
+    let wetland_flow = config.wetland_flow * points.len() as f32;
+
     (0..points.len()).map(|p| {
-        let temperature = temperature(points[p].y);
-        let ocean_temperature = (temperature + 2.0 * ocean_temperatures[p] + atmospheric_temperatures[p]) / 4.0;
-        let land_temperature = (temperature + 2.0 * atmospheric_temperatures[p]) / 3.0;
+        let y = points[p].y;
+        let ocean_temperature = sea_temperature(y, ocean_temperatures[p], atmospheric_temperatures[p]);
+        let land_temperature = land_temperature(y, atmospheric_temperatures[p]);
         let precipitation = precipitation[p];
+        let coastal = adjacencies[p].iter().any(|q| terrain[*q].is_ocean());
 
         match terrain[p] {
+            Terrain::Mountain if land_temperature < config.snowline_temperature => Biome::Snow,
             Terrain::Mountain => Biome::Mountain,
-            Terrain::Land => land(land_temperature, precipitation),
-            Terrain::Shallow => shallow(ocean_temperature),
-            Terrain::Ocean => ocean(ocean_temperature),
+            Terrain::Highland if land_temperature < config.alpine_temperature => Biome::Alpine,
+            Terrain::Land if coastal
+                && land_temperature > config.mangrove_temperature
+                && precipitation > config.mangrove_precipitation => Biome::Mangrove,
+            Terrain::Land if flow[p] > wetland_flow => Biome::Wetland,
+            Terrain::Highland | Terrain::Hills | Terrain::Land => {
+                whittaker(land_temperature, precipitation, &config.table)
+            },
+            Terrain::Lake => Biome::Lake,
+            Terrain::SaltLake => Biome::SaltLake,
+            Terrain::Shelf | Terrain::Slope | Terrain::Ocean | Terrain::Trench => {
+                water(terrain[p], ocean_temperature, config)
+            },
         }
     }).collect()
 }
 
-fn land(temperature: f32, precipitation: f32) -> Biome {
-    if temperature < SNOW_TEMPERATURE {
-        Biome::Snow
-    } else if temperature < TUNDRA_TEMPERATURE {
-        Biome::Tundra
-    } else if precipitation < ARID_PRECIPITATION {
-        Biome::Desert
-    } else if temperature > TROPICAL_TEMPERATURE {
-        tropical(precipitation)
-    } else {
-        temperate(precipitation)
-    }
-}
-
-fn tropical(precipitation: f32) -> Biome {
-    if precipitation > RAINFOREST_PRECIPITATION {
-        Biome::Rainforest
-    } else {
-        Biome::Plains
-    }
-}
-
-fn temperate(precipitation: f32) -> Biome {
-    if precipitation > FOREST_PRECIPITATION {
-        Biome::Forest
-    } else {
-        Biome::Grassland
-    }
+fn whittaker(temperature: f32, precipitation: f32, table: &Vec<BiomeRule>) -> Biome {
+    table.iter()
+        .find(|rule| {
+            temperature < rule.max_temperature && precipitation < rule.max_precipitation
+        })
+        .or(table.last())
+        .map_or(Biome::Grassland, |rule| rule.biome)
 }
 
-fn shallow(temperature: f32) -> Biome {
-    if temperature < ICE_TEMPERATURE {
-        Biome::Ice
+fn water(terrain: Terrain, temperature: f32, config: &BiomeConfig) -> Biome {
+    if temperature < config.pack_ice_temperature {
+        Biome::PackIce
+    } else if temperature < config.sea_ice_temperature {
+        Biome::SeaIce
+    } else if terrain != Terrain::Shelf {
+        Biome::Ocean
+    } else if temperature > config.coral_temperature {
+        Biome::CoralReef
     } else {
         Biome::Shallow
     }
-}
-
-fn ocean(temperature: f32) -> Biome {
-    if temperature < ICE_TEMPERATURE {
-        Biome::Ice
-    } else {
-        Biome::Ocean
-    }
 }
\ No newline at end of file
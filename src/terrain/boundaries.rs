@@ -0,0 +1,99 @@
+use bevy::math::Vec3;
+use serde::{Deserialize, Serialize};
+use triangulation::Triangulation;
+
+use crate::config::TectonicsConfig;
+use crate::terrain::tectonics::Plate;
+
+/// How two plates move relative to each other across a boundary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundaryKind {
+    /// Converging plates where one dives under the other, forming a trench.
+    Subduction,
+    /// Converging continental plates, neither of which can sink.
+    Collision,
+    /// Plates moving apart, forming a ridge or rift.
+    Divergent,
+    /// Plates sliding past each other.
+    Transform,
+}
+
+/// The edge of the Voronoi cells of two neighbouring points on different
+/// plates.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoundarySegment {
+    pub cells: [usize; 2],
+    pub plates: [usize; 2],
+    pub kind: BoundaryKind,
+    /// How fast the plates approach each other, negative when separating.
+    pub convergence: f32,
+    /// How fast the plates slide past each other.
+    pub shear: f32,
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl BoundaryKind {
+    pub const fn color(&self) -> [f32; 3] {
+        match self {
+            Self::Subduction => [0.8, 0.2, 0.8],
+            Self::Collision => [1.0, 0.1, 0.1],
+            Self::Divergent => [0.1, 0.4, 1.0],
+            Self::Transform => [1.0, 1.0, 0.1],
+        }
+    }
+}
+
+pub fn boundaries(
+    template: &Triangulation,
+    edges: &Vec<Vec<usize>>,
+    plates: &Vec<Plate>,
+    plate_ids: &Vec<usize>,
+    velocities: &Vec<Vec3>,
+    config: &TectonicsConfig,
+) -> Vec<BoundarySegment> {
+    let points = &template.points;
+    let mut boundaries = vec![];
+    for p in 0..points.len() {
+        for e in &edges[p] {
+            let q = template.triangles[*e];
+            if q < p || plate_ids[p] == plate_ids[q] { continue }
+
+            let normal = (points[q] - points[p]).normalize();
+            let relative_velocity = velocities[q] - velocities[p];
+            let convergence = -relative_velocity.dot(normal);
+            let shear = (relative_velocity + convergence * normal).length();
+
+            let plate_1 = &plates[plate_ids[p]];
+            let plate_2 = &plates[plate_ids[q]];
+            let kind = if shear > config.transform_ratio * convergence.abs() {
+                BoundaryKind::Transform
+            } else if convergence < 0.0 {
+                BoundaryKind::Divergent
+            } else if plate_1.continental && plate_2.continental {
+                BoundaryKind::Collision
+            } else {
+                BoundaryKind::Subduction
+            };
+
+            boundaries.push(BoundarySegment {
+                cells: [p, q],
+                plates: [plate_ids[p], plate_ids[q]],
+                kind,
+                convergence,
+                shear,
+                start: centroid(template, e / 3),
+                end: centroid(template, template.half_edges[*e] / 3),
+            });
+        }
+    }
+
+    return boundaries;
+}
+
+fn centroid(template: &Triangulation, t: usize) -> Vec3 {
+    let a = template.points[template.triangles[3*t]];
+    let b = template.points[template.triangles[3*t+1]];
+    let c = template.points[template.triangles[3*t+2]];
+    ((a + b + c) / 3.0).normalize()
+}
\ No newline at end of file
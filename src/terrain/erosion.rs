@@ -0,0 +1,120 @@
+use bevy::math::Vec3;
+
+use crate::config::ErosionConfig;
+use crate::terrain::Terrain;
+
+/// Routes rain downhill over the land and lets the water carve into steep
+/// slopes and drop its sediment where it slows, repeated
+/// `config.hydraulic_iterations` times.
+///
+/// Each cell drains to its lowest neighbour. Water carries sediment up to a
+/// capacity that grows with its volume and the slope, eroding the cell when
+/// below capacity and depositing onto it when above. Sediment reaching the
+/// sea builds up a delta on the first ocean cell, no higher than
+/// `config.delta_height`, and what doesn't fit is shared between that cell's
+/// ocean neighbours. Anything left over is carried out to sea.
+pub fn hydraulic_erosion(
+    points: &Vec<Vec3>,
+    adjacencies: &Vec<Vec<usize>>,
+    terrain: &Vec<Terrain>,
+    heights: &mut Vec<f32>,
+    precipitation: &Vec<f32>,
+    config: &ErosionConfig,
+) {
+    let mut land: Vec<usize> = (0..points.len()).filter(|p| {
+        terrain[*p].is_land()
+    }).collect();
+
+    for _ in 0..config.hydraulic_iterations {
+        land.sort_by(|a, b| heights[*b].total_cmp(&heights[*a]));
+
+        let mut water = precipitation.clone();
+        let mut sediment = vec![0.0].repeat(points.len());
+        for p in &land {
+            let p = *p;
+            let lowest = adjacencies[p].iter()
+                .copied()
+                .min_by(|a, b| heights[*a].total_cmp(&heights[*b]));
+            let q = match lowest {
+                Some(q) if heights[q] < heights[p] => q,
+                _ => {
+                    heights[p] += sediment[p];
+                    continue;
+                },
+            };
+
+            let slope = (heights[p] - heights[q]) / points[p].distance(points[q]);
+            let capacity = config.capacity * water[p].sqrt() * slope;
+            if sediment[p] > capacity {
+                let deposit = config.deposition_rate * (sediment[p] - capacity);
+                heights[p] += deposit;
+                sediment[p] -= deposit;
+            } else {
+                let floor = heights[q].max(0.0);
+                let erosion = (config.erosion_rate * (capacity - sediment[p]))
+                    .min(0.5 * (heights[p] - floor))
+                    .max(0.0);
+                heights[p] -= erosion;
+                sediment[p] += erosion;
+            }
+
+            water[q] += water[p];
+            sediment[q] += sediment[p];
+            if !terrain[q].is_land() {
+                let excess = deposit(heights, q, sediment[q], config.delta_height);
+                let sea: Vec<usize> = adjacencies[q].iter().copied().filter(|r| {
+                    !terrain[*r].is_land()
+                }).collect();
+                for r in &sea {
+                    deposit(heights, *r, excess / sea.len() as f32, config.delta_height);
+                }
+                sediment[q] = 0.0;
+            }
+        }
+    }
+}
+
+/// Raises `heights[p]` by up to `amount` without going above `ceiling`,
+/// returning what didn't fit.
+fn deposit(heights: &mut Vec<f32>, p: usize, amount: f32, ceiling: f32) -> f32 {
+    let deposited = amount.min((ceiling - heights[p]).max(0.0));
+    heights[p] += deposited;
+    amount - deposited
+}
+
+/// Slides material off any cell steeper than `config.talus` towards its
+/// lower neighbours, repeated `config.thermal_iterations` times, leaving
+/// scree slopes at the talus angle.
+///
+/// Every cell moves `config.thermal_rate` of half its steepest excess slope,
+/// shared between its neighbours by how far each lies beyond the talus angle.
+pub fn thermal_erosion(
+    points: &Vec<Vec3>,
+    adjacencies: &Vec<Vec<usize>>,
+    heights: &mut Vec<f32>,
+    config: &ErosionConfig,
+) {
+    for _ in 0..config.thermal_iterations {
+        let mut changes = vec![0.0].repeat(points.len());
+        for p in 0..points.len() {
+            let excesses: Vec<f32> = adjacencies[p].iter().map(|q| {
+                let distance = points[p].distance(points[*q]);
+                (heights[p] - heights[*q] - config.talus * distance).max(0.0)
+            }).collect();
+
+            let total: f32 = excesses.iter().sum();
+            if total == 0.0 { continue }
+
+            let steepest = excesses.iter().copied().fold(0.0, f32::max);
+            let moved = config.thermal_rate * 0.5 * steepest;
+            changes[p] -= moved;
+            for (q, excess) in adjacencies[p].iter().zip(&excesses) {
+                changes[*q] += moved * excess / total;
+            }
+        }
+
+        for p in 0..points.len() {
+            heights[p] += changes[p];
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use bevy::math::Vec3;
+use noise::{NoiseFn, Perlin};
+use rand::Rng;
+
+use crate::config::{NoiseConfig, NoiseKind};
+
+const WARP_OFFSETS: [[f64; 3]; 3] = [
+    [5.2, 1.3, 7.1],
+    [1.7, 9.2, 3.4],
+    [8.3, 2.8, 4.6],
+];
+const RIDGE_GAIN: f64 = 2.0;
+
+/// Multi-octave Perlin noise sampled on the sphere.
+pub struct Fractal {
+    perlin: Perlin,
+    config: NoiseConfig,
+}
+
+impl Fractal {
+    pub fn new(config: &NoiseConfig, rng: &mut impl Rng) -> Self {
+        Self {
+            perlin: Perlin::new(rng.random()),
+            config: config.clone(),
+        }
+    }
+
+    /// The noise at `point`, scaled to lie roughly within
+    /// `-amplitude..amplitude`.
+    pub fn get(&self, point: Vec3) -> f32 {
+        let mut point = [point.x as f64, point.y as f64, point.z as f64];
+
+        if self.config.warp != 0.0 {
+            let warp = WARP_OFFSETS.map(|offset| {
+                let frequency = self.config.frequency;
+                self.perlin.get([
+                    frequency * point[0] + offset[0],
+                    frequency * point[1] + offset[1],
+                    frequency * point[2] + offset[2],
+                ])
+            });
+            for i in 0..3 {
+                point[i] += self.config.warp * warp[i];
+            }
+        }
+
+        let noise = match self.config.kind {
+            NoiseKind::Fbm => self.fbm(point),
+            NoiseKind::Ridged => self.ridged(point),
+        };
+        self.config.amplitude * noise as f32
+    }
+
+    fn octave(&self, point: [f64; 3], frequency: f64) -> f64 {
+        self.perlin.get(point.map(|x| frequency * x))
+    }
+
+    /// Sums octaves of increasing frequency and decreasing amplitude,
+    /// normalised so a single octave is plain Perlin noise.
+    fn fbm(&self, point: [f64; 3]) -> f64 {
+        let mut frequency = self.config.frequency;
+        let mut amplitude = 1.0;
+        let mut total = 0.0;
+        let mut noise = 0.0;
+        for _ in 0..self.config.octaves.max(1) {
+            noise += amplitude * self.octave(point, frequency);
+            total += amplitude;
+            frequency *= self.config.lacunarity;
+            amplitude *= self.config.persistence;
+        }
+        noise / total
+    }
+
+    /// Sums octaves of sharp ridges, each weighted by the octave before it so
+    /// detail gathers along the crests.
+    fn ridged(&self, point: [f64; 3]) -> f64 {
+        let mut frequency = self.config.frequency;
+        let mut amplitude = 1.0;
+        let mut weight = 1.0;
+        let mut total = 0.0;
+        let mut noise = 0.0;
+        for _ in 0..self.config.octaves.max(1) {
+            let ridge = 1.0 - self.octave(point, frequency).abs();
+            let signal = weight * ridge * ridge;
+            weight = (RIDGE_GAIN * signal).clamp(0.0, 1.0);
+
+            noise += amplitude * signal;
+            total += amplitude;
+            frequency *= self.config.lacunarity;
+            amplitude *= self.config.persistence;
+        }
+        2.0 * noise / total - 1.0
+    }
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+use bevy::math::{Quat, Vec3};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::TectonicsConfig;
+use crate::random::random_units;
+use crate::template::{nearest, spacing};
+use crate::terrain::tectonics::Plate;
+
+/// A volcano raised by a mantle hotspot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Volcano {
+    pub cell: usize,
+    pub plate: usize,
+    /// The position of the hotspot when this volcano formed above it.
+    pub position: Vec3,
+    /// How many chain spacings the plate has carried the volcano away from
+    /// its hotspot. Only volcanoes of age 0 are still erupting.
+    pub age: usize,
+    /// The height the volcano added to its cell.
+    pub height: f32,
+}
+
+impl Volcano {
+    pub fn active(&self) -> bool {
+        self.age == 0
+    }
+}
+
+/// Places `config.hotspots` hotspots fixed in the planet's frame and raises a
+/// chain of volcanoes over each, trailing behind the plate above it as it
+/// rotates, with older volcanoes eroded lower.
+pub fn hotspots(
+    points: &Vec<Vec3>,
+    adjacencies: &Vec<Vec<usize>>,
+    plates: &Vec<Plate>,
+    plate_ids: &Vec<usize>,
+    heights: &mut Vec<f32>,
+    config: &TectonicsConfig,
+    rng: &mut impl Rng,
+) -> Vec<Volcano> {
+    let angle = config.chain_spacing * spacing(points.len());
+
+    let mut volcanoes = vec![];
+    for hotspot in random_units(rng, config.hotspots) {
+        let cell = nearest(points, adjacencies, hotspot, 0);
+        let plate = plate_ids[cell];
+        let rotation = Quat::from_axis_angle(plates[plate].rotation, angle);
+
+        let mut position = hotspot;
+        let mut height = config.hotspot_height;
+        for age in 0..config.chain_length {
+            let cell = nearest(points, adjacencies, position, cell);
+            if plate_ids[cell] != plate { break }
+
+            heights[cell] += height;
+            for q in &adjacencies[cell] {
+                heights[*q] += config.hotspot_flank * height;
+            }
+            volcanoes.push(Volcano { cell, plate, position, age, height });
+
+            position = rotation * position;
+            height *= config.chain_decay;
+        }
+    }
+
+    for height in heights.iter_mut() {
+        *height = height.min(config.max_height);
+    }
+
+    return volcanoes;
+}
\ No newline at end of file
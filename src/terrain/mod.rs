@@ -1,47 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::TerrainConfig;
+
 pub mod biomes;
 pub mod borders;
+pub mod boundaries;
+pub mod erosion;
+mod fractal;
+pub mod hotspots;
+mod simulation;
 pub mod tectonics;
 
-const MOUNTAIN_HEIGHT: f32 = 0.03;
-pub const LAND_HEIGHT: f32 = -0.01;
-pub const SHALLOW_HEIGHT: f32 = -0.02;
-
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Terrain {
     Mountain,
+    /// High plateaus and foothills below the mountains.
+    Highland,
+    Hills,
     Land,
-    Shallow,
+    /// A lake that overflows towards the sea.
+    Lake,
+    /// A closed lake that loses all its water to evaporation.
+    SaltLake,
+    /// The shallow sea over the edges of the continents.
+    Shelf,
+    /// The drop from the shelf down to the ocean floor.
+    Slope,
+    /// The abyssal plain.
     Ocean,
+    /// The deepest ocean, where crust is pushed down.
+    Trench,
 }
 
+/// Classifies every cell by its height, raising low land up to sea level.
+/// Heights below the sea are kept as they are.
 pub fn terrain(
     heights: &mut Vec<f32>,
+    config: &TerrainConfig,
 ) -> Vec<Terrain> {
     (0..heights.len()).map(|p| {
-        let terrain = Terrain::at_height(heights[p]);
-        match terrain {
-            Terrain::Land => {
-                heights[p] = heights[p].max(0.0)
-            },
-            Terrain::Shallow => {
-                heights[p] = SHALLOW_HEIGHT
-            },
-            _ => {},
+        let terrain = Terrain::at_height(heights[p], config);
+        if terrain == Terrain::Land {
+            heights[p] = heights[p].max(0.0);
         }
         terrain
     }).collect()
 }
 
 impl Terrain {
-    pub fn at_height(height: f32) -> Self {
-        if height > MOUNTAIN_HEIGHT {
+    pub const ALL: [Terrain; 10] = [
+        Self::Mountain,
+        Self::Highland,
+        Self::Hills,
+        Self::Land,
+        Self::Lake,
+        Self::SaltLake,
+        Self::Shelf,
+        Self::Slope,
+        Self::Ocean,
+        Self::Trench,
+    ];
+
+    /// A fixed number for the terrain in exported files. Codes are never
+    /// renumbered or reused, so a new variant takes the next free code.
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::Mountain => 0,
+            Self::Highland => 1,
+            Self::Hills => 2,
+            Self::Land => 3,
+            Self::Lake => 4,
+            Self::SaltLake => 5,
+            Self::Shelf => 6,
+            Self::Slope => 7,
+            Self::Ocean => 8,
+            Self::Trench => 9,
+        }
+    }
+
+    pub fn at_height(height: f32, config: &TerrainConfig) -> Self {
+        if height > config.mountain_height {
             Self::Mountain
-        } else if height > LAND_HEIGHT {
+        } else if height > config.highland_height {
+            Self::Highland
+        } else if height > config.hills_height {
+            Self::Hills
+        } else if height > config.land_height {
             Self::Land
-        } else if height > SHALLOW_HEIGHT {
-            Self::Shallow
-        } else {
+        } else if height > config.shelf_height {
+            Self::Shelf
+        } else if height > config.slope_height {
+            Self::Slope
+        } else if height > config.trench_height {
             Self::Ocean
+        } else {
+            Self::Trench
         }
     }
+
+    /// Whether the terrain is dry land, which rivers drain over.
+    pub const fn is_land(&self) -> bool {
+        matches!(self, Self::Mountain | Self::Highland | Self::Hills | Self::Land)
+    }
+
+    /// Whether the terrain is part of the sea rather than land or a lake.
+    pub const fn is_ocean(&self) -> bool {
+        matches!(self, Self::Shelf | Self::Slope | Self::Ocean | Self::Trench)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+use bevy::math::{Quat, Vec3};
+
+use crate::config::TectonicsConfig;
+use crate::template::{nearest, spacing};
+use crate::terrain::tectonics::Plate;
+
+/// A piece of crust carried along by its plate.
+#[derive(Clone, Copy)]
+struct Parcel {
+    position: Vec3,
+    cell: usize,
+    plate: usize,
+    height: f32,
+    continental: bool,
+}
+
+/// Moves the plates along their rotations for `config.steps` steps.
+///
+/// Crust is tracked as parcels that rotate with their plate and are then
+/// snapped back onto the nearest cell. Cells left empty rift open into new
+/// oceanic crust, and cells reached by several parcels keep the most buoyant
+/// one, which is uplifted by the collision or by the crust subducting under
+/// it.
+pub fn simulate(
+    points: &Vec<Vec3>,
+    adjacencies: &Vec<Vec<usize>>,
+    plates: &mut Vec<Plate>,
+    plate_ids: &mut Vec<usize>,
+    heights: &mut Vec<f32>,
+    config: &TectonicsConfig,
+) {
+    let angle = config.step_distance * spacing(points.len());
+    let rotations: Vec<Quat> = plates.iter().map(|plate| {
+        Quat::from_axis_angle(plate.rotation, angle)
+    }).collect();
+
+    let mut parcels: Vec<Parcel> = (0..points.len()).map(|p| {
+        let plate = plate_ids[p];
+        Parcel {
+            position: points[p],
+            cell: p,
+            plate,
+            height: heights[p],
+            continental: plates[plate].continental,
+        }
+    }).collect();
+
+    for _ in 0..config.steps {
+        let mut occupants = vec![vec![]; points.len()];
+        for (i, parcel) in parcels.iter_mut().enumerate() {
+            parcel.position = (rotations[parcel.plate] * parcel.position).normalize();
+            parcel.cell = nearest(points, adjacencies, parcel.position, parcel.cell);
+            occupants[parcel.cell].push(i);
+        }
+
+        parcels = (0..points.len()).map(|p| {
+            let mut parcel = match occupants[p].as_slice() {
+                [] => Parcel {
+                    position: points[p],
+                    cell: p,
+                    plate: plate_ids[p],
+                    height: config.rift_height,
+                    continental: false,
+                },
+                [i] => parcels[*i],
+                arriving => collide(&parcels, arriving, config),
+            };
+
+            if !parcel.continental {
+                parcel.height += config.subsidence * (config.ocean_height - parcel.height);
+            }
+            parcel.height = parcel.height.min(config.max_height);
+            parcel
+        }).collect();
+
+        for parcel in &parcels {
+            plate_ids[parcel.cell] = parcel.plate;
+            heights[parcel.cell] = parcel.height;
+        }
+    }
+
+    for plate in plates.iter_mut() {
+        plate.cells.clear();
+    }
+    for p in 0..points.len() {
+        plates[plate_ids[p]].cells.push(p);
+    }
+}
+
+fn collide(parcels: &Vec<Parcel>, arriving: &[usize], config: &TectonicsConfig) -> Parcel {
+    let overriding = *arriving.iter()
+        .max_by(|a, b| {
+            let a = &parcels[**a];
+            let b = &parcels[**b];
+            a.continental.cmp(&b.continental).then(a.height.total_cmp(&b.height))
+        })
+        .unwrap();
+
+    let mut parcel = parcels[overriding];
+    for i in arriving {
+        if *i == overriding { continue }
+        parcel.height += if parcels[*i].continental {
+            config.collision_uplift
+        } else {
+            config.subduction_uplift
+        };
+    }
+    return parcel;
+}
\ No newline at end of file
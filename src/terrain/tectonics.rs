@@ -1,64 +1,72 @@
 use bevy::math::Vec3;
-use math::vec3::{random_unit, random_units};
-use noise::{NoiseFn, Perlin};
-use rand::random_range;
-
-const NUM_PLATES: usize = 20;
-
-pub const OCEAN_HEIGHT: f32 = -0.03;
-const ISLAND_HEIGHT: f32 = -0.01;
-
-const MAX_HEIGHT: f32 = 0.09;
-const CONTINENT_HEIGHT: f32 = 0.01;
-const CONTINENT_SLOPE: f32 = 0.05;
-
-const NOISE_DETAIL: f64 = 1.0;
-const NOISE_HEIGHT: f32 = 0.02;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::config::TectonicsConfig;
+use crate::random::{random_unit, random_units};
+use crate::template::nearest;
+use crate::terrain::fractal::Fractal;
+use crate::terrain::hotspots::{Volcano, hotspots};
+use crate::terrain::simulation::simulate;
+
+/// A rigid piece of crust rotating about an axis through the planet's centre.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Plate {
+    /// The cells on the plate, starting from the cell it grew from unless
+    /// the plates have been moved by [`simulate`].
+    pub cells: Vec<usize>,
+    /// The unit axis the plate rotates about.
+    pub rotation: Vec3,
+    pub continental: bool,
+    pub color: [f32; 3],
+}
 
-const CONVERGENT_STRESS: f32 = 0.2;
-const DIVERGENT_STRESS: f32 = 0.05;
-const OCEAN_STRESS: f32 = 0.4;
+pub struct Tectonics {
+    pub heights: Vec<f32>,
+    pub plates: Vec<Plate>,
+    pub plate_ids: Vec<usize>,
+    pub velocities: Vec<Vec3>,
+    pub stress: Vec<f32>,
+    pub volcanoes: Vec<Volcano>,
+}
 
 pub fn tectonics(
     points: &Vec<Vec3>, 
     adjacencies: &Vec<Vec<usize>>,
-) -> Vec<f32> {
+    config: &TectonicsConfig,
+    rng: &mut impl Rng,
+) -> Tectonics {
     let mut plates = vec![];
     let mut queue = vec![];
-    let mut colors = vec![];
-    let mut rotations = vec![];
-
-    for point in random_units(NUM_PLATES) {
-        let p = (0..points.len())
-            .min_by(|a, b| {
-                point
-                    .distance_squared(points[*a])
-                    .total_cmp(&point.distance_squared(points[*b]))
-            })
-            .unwrap();
-        plates.push(vec![]);
+
+    for point in random_units(rng, config.num_plates) {
+        let p = nearest(points, adjacencies, point, 0);
         queue.push((queue.len(), p));
 
         let color = [
-            random_range(0.0..1.0),
-            random_range(0.0..1.0),
-            random_range(0.0..1.0),
+            rng.random_range(0.0..1.0),
+            rng.random_range(0.0..1.0),
+            rng.random_range(0.0..1.0),
         ];
-        colors.push(color);
-
-        rotations.push(random_unit());
+        plates.push(Plate {
+            cells: vec![],
+            rotation: random_unit(rng),
+            continental: false,
+            color,
+        });
     }
 
     let mut assigned = vec![false].repeat(points.len());
     let mut velocities = vec![Vec3::ZERO].repeat(points.len());
     while !queue.is_empty() {
-        let (plate, p) = queue.swap_remove(random_range(0..queue.len()));
+        let (plate, p) = queue.swap_remove(rng.random_range(0..queue.len()));
         
         if !assigned[p] {
             assigned[p] = true;
-            plates[plate].push(p);
+            plates[plate].cells.push(p);
 
-            let velocity = rotations[plate].cross(points[p]);
+            let velocity = plates[plate].rotation.cross(points[p]);
             velocities[p] = velocity;
 
             for q in &adjacencies[p] {
@@ -69,37 +77,107 @@ pub fn tectonics(
         }
     }
 
-    let mut plates: Vec<Vec<usize>> = plates.into_iter().filter(|plate | {
-        !plate.is_empty()
+    let mut plates: Vec<Plate> = plates.into_iter().filter(|plate | {
+        !plate.cells.is_empty()
     }).collect();
     plates.sort_by(|a, b| {
-        b.len().cmp(&a.len())
+        b.cells.len().cmp(&a.cells.len())
     });
 
-    let mut polar_plate = 0;
-    let mut pole_latitude = 0.0;
+    let continental = continental(points, &plates, config, rng);
+
+    let mut plate_ids = vec![0].repeat(points.len());
     for plate in 0..plates.len() {
-        let latitude = points[plates[plate][0]].y.abs();
-        if latitude > pole_latitude {
-            polar_plate = plate;
-            pole_latitude = latitude;
+        plates[plate].continental = continental[plate];
+        for p in &plates[plate].cells {
+            plate_ids[*p] = plate;
+        }
+    }
+
+    let mut stress = stress(points, adjacencies, &plates, &velocities, config);
+
+    let continental_noise = Fractal::new(&config.continental_noise, rng);
+    let oceanic_noise = Fractal::new(&config.oceanic_noise, rng);
+    let mut heights = vec![0.0].repeat(points.len());
+    for plate in &plates {
+        let plate_centre = points[plate.cells[0]];
+        let noise = if plate.continental {
+            &continental_noise
+        } else {
+            &oceanic_noise
+        };
+
+        for p in &plate.cells {
+            let relief = noise.get(points[*p]);
+            heights[*p] += relief;
+
+            heights[*p] += stress[*p];
+
+            if plate.continental {
+                let distance = plate_centre.distance_squared(points[*p]);
+                heights[*p] += config.continent_height - config.continent_slope * distance;
+            } else {
+                heights[*p] += config.ocean_height;
+                if heights[*p] < config.island_height {
+                    heights[*p] = config.ocean_height + relief
+                        - config.trench_depth * stress[*p].max(0.0);
+                }
+            }
+
+            if heights[*p] > config.max_height {
+                heights[*p] = config.max_height;
+            }
+        }
+    }
+
+    if config.steps > 0 {
+        simulate(points, adjacencies, &mut plates, &mut plate_ids, &mut heights, config);
+        for p in 0..points.len() {
+            velocities[p] = plates[plate_ids[p]].rotation.cross(points[p]);
         }
+        stress = self::stress(points, adjacencies, &plates, &velocities, config);
     }
-    plates.swap(2, polar_plate);
 
+    let volcanoes = hotspots(
+        points, 
+        adjacencies, 
+        &plates, 
+        &plate_ids, 
+        &mut heights, 
+        config, 
+        rng,
+    );
+
+    return Tectonics {
+        heights,
+        plates,
+        plate_ids,
+        velocities,
+        stress,
+        volcanoes,
+    };
+}
+
+fn stress(
+    points: &Vec<Vec3>,
+    adjacencies: &Vec<Vec<usize>>,
+    plates: &Vec<Plate>,
+    velocities: &Vec<Vec3>,
+    config: &TectonicsConfig,
+) -> Vec<f32> {
     let mut stress = vec![0.0].repeat(points.len());
-    for plate in 0..plates.len() {
-        for p in &plates[plate] {
+    for plate in plates {
+        for p in &plate.cells {
             let velocity_1 = velocities[*p];
             for q in &adjacencies[*p] {
                 let velocity_2 = velocities[*q];
                 let distance = points[*p] - points[*q];
                 let mut added_stress = distance.dot(velocity_2 - velocity_1);
-                if !continental(plate) { added_stress *= OCEAN_STRESS }
+                if !plate.continental { added_stress *= config.ocean_stress }
                 if added_stress > 0.0 {
-                    added_stress *= CONVERGENT_STRESS;
+                    added_stress *= config.convergent_stress;
                 } else {
-                    added_stress *= DIVERGENT_STRESS;
+                    added_stress *= config.divergent_stress;
                 }
                 stress[*p] += added_stress;
                 stress[*q] += added_stress;
@@ -107,43 +185,39 @@ pub fn tectonics(
         }
     }
 
-    let perlin = Perlin::new(0);
-    let mut heights = vec![0.0].repeat(points.len());
-    for plate in 0..plates.len() {
-        let plate_centre = points[plates[plate][0]];
-
-        for p in &plates[plate] {
-            let noise_point = [
-                NOISE_DETAIL * points[*p].x as f64,
-                NOISE_DETAIL * points[*p].y as f64,
-                NOISE_DETAIL * points[*p].z as f64,
-            ];
-            let noise = perlin.get(noise_point) as f32;
-            
-            heights[*p] += NOISE_HEIGHT * noise;
-
-            let stress = stress[*p];
-            heights[*p] += stress;
-
-            if continental(plate) {
-                let distance = plate_centre.distance_squared(points[*p]);
-                heights[*p] += CONTINENT_HEIGHT - CONTINENT_SLOPE * distance;
-            } else {
-                heights[*p] += OCEAN_HEIGHT;
-                if heights[*p] < ISLAND_HEIGHT {
-                    heights[*p] = OCEAN_HEIGHT;
-                }
-            }
+    return stress;
+}
 
-            if heights[*p] > MAX_HEIGHT {
-                heights[*p] = MAX_HEIGHT;
-            }
-        }
+/// Picks continental plates, visiting them in a random order and keeping
+/// each one that brings the continental area closer to the target fraction.
+fn continental(
+    points: &Vec<Vec3>,
+    plates: &Vec<Plate>,
+    config: &TectonicsConfig,
+    rng: &mut impl Rng,
+) -> Vec<bool> {
+    let mut continental = vec![false].repeat(plates.len());
+    let mut order: Vec<usize> = (0..plates.len()).collect();
+    order.shuffle(rng);
+
+    if config.oceanic_poles {
+        let polar_plate = (0..plates.len()).max_by(|a, b| {
+            let latitude_a = points[plates[*a].cells[0]].y.abs();
+            let latitude_b = points[plates[*b].cells[0]].y.abs();
+            latitude_a.total_cmp(&latitude_b)
+        });
+        order.retain(|plate| Some(*plate) != polar_plate);
     }
 
-    return heights;
-}
+    let target = config.continental_fraction * points.len() as f32;
+    let mut area = 0.0;
+    for plate in order {
+        let new_area = area + plates[plate].cells.len() as f32;
+        if (new_area - target).abs() < (area - target).abs() {
+            continental[plate] = true;
+            area = new_area;
+        }
+    }
 
-const fn continental(i: usize) -> bool {
-    i % 3 == 1
+    return continental;
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+//! Conversions from the generator's dimensionless values to physical units.
+//!
+//! Heights are generated as an exaggerated fraction of the planet's radius,
+//! temperatures from about -1 at the poles to 1 at the equator and
+//! precipitation from 0 to 1. The scales in [`UnitsConfig`] map these onto
+//! metres, °C and mm of rain per year so they can be compared with Earth
+//! data.
+
+use crate::Planet;
+use crate::config::UnitsConfig;
+use crate::weather::temperatures::{land_temperature, sea_temperature};
+
+impl UnitsConfig {
+    /// Converts a height measured as a fraction of the planet's radius to
+    /// metres above sea level, removing the exaggeration.
+    pub fn metres(&self, height: f32) -> f32 {
+        (height - self.sea_level) * self.radius / self.exaggeration
+    }
+
+    /// Converts metres above sea level back to an exaggerated height above
+    /// sea level, as a fraction of the planet's radius.
+    pub fn relief(&self, metres: f32) -> f32 {
+        metres * self.exaggeration / self.radius
+    }
+
+    /// Converts a dimensionless temperature to °C.
+    pub fn celsius(&self, temperature: f32) -> f32 {
+        self.mean_temperature + self.temperature_range * temperature
+    }
+
+    /// Converts a dimensionless precipitation to mm of rain per year.
+    pub fn millimetres_per_year(&self, precipitation: f32) -> f32 {
+        self.water_budget * precipitation
+    }
+}
+
+impl Planet {
+    /// The year-round surface temperature of cell `p` in °C, from the same
+    /// blend of latitude, ocean and atmosphere the biomes and climate zones
+    /// are picked by.
+    pub fn temperature_celsius(&self, p: usize) -> f32 {
+        if self.terrain[p].is_ocean() {
+            self.ocean_temperature_celsius(p)
+        } else {
+            let y = self.points[p].normalize().y;
+            self.config.units.celsius(land_temperature(y, self.atmospheric_temperatures[p]))
+        }
+    }
+
+    /// The sea surface temperature of cell `p` in °C.
+    pub fn ocean_temperature_celsius(&self, p: usize) -> f32 {
+        let y = self.points[p].normalize().y;
+        let temperature = sea_temperature(
+            y,
+            self.ocean_temperatures[p],
+            self.atmospheric_temperatures[p],
+        );
+        self.config.units.celsius(temperature)
+    }
+
+    /// The rainfall on cell `p` in mm per year.
+    pub fn rainfall(&self, p: usize) -> f32 {
+        self.config.units.millimetres_per_year(self.precipitation[p])
+    }
+}
\ No newline at end of file
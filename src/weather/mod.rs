@@ -1,5 +1,6 @@
 use bevy::math::Vec3;
 
+use crate::config::WeatherConfig;
 use crate::terrain::Terrain;
 use crate::weather::atmosphere::atmosphere;
 use crate::weather::fluid::velocities;
@@ -14,25 +15,20 @@ mod ocean;
 pub mod temperatures;
 mod precipitation;
 
-const OCEAN_ITERATIONS: f32 = 10.0;
-const ATMOSPHERE_ITERATIONS: f32 = 1.0;
-const OCEAN_ADVECTION: f32 = 0.3;
-const ATMOSPHERE_ADVECTION: f32 = 0.2;
-const PRECIPITATION_ITERATIONS: f32 = 0.5;
-
 pub fn weather(
     points: &Vec<Vec3>,
     half_edges: &Vec<usize>,
     terrain: &Vec<Terrain>,
     edges: &Vec<Vec<usize>>, 
     adjacencies: &Vec<Vec<usize>>, 
+    config: &WeatherConfig,
 ) -> (Vec<Vec3>, Vec<f32>, Vec<Vec3>, Vec<f32>, Vec<f32>) {
     let iteration_multiplier = (points.len() as f32).sqrt();
-    let ocean_iterations = (OCEAN_ITERATIONS * iteration_multiplier) as usize;
-    let atmosphere_iterations = (ATMOSPHERE_ITERATIONS * iteration_multiplier) as usize;
-    let ocean_advection = (OCEAN_ADVECTION * iteration_multiplier) as usize;
-    let atmosphere_advection = (ATMOSPHERE_ADVECTION * iteration_multiplier) as usize;
-    let precipitation_iterations = (PRECIPITATION_ITERATIONS * iteration_multiplier) as usize;
+    let ocean_iterations = (config.ocean_iterations * iteration_multiplier) as usize;
+    let atmosphere_iterations = (config.atmosphere_iterations * iteration_multiplier) as usize;
+    let ocean_advection = (config.ocean_advection * iteration_multiplier) as usize;
+    let atmosphere_advection = (config.atmosphere_advection * iteration_multiplier) as usize;
+    let precipitation_iterations = (config.precipitation_iterations * iteration_multiplier) as usize;
 
     let ocean_currents = ocean(
         points,
@@ -41,8 +37,9 @@ pub fn weather(
         adjacencies, 
         terrain,
         ocean_iterations,
+        config.shelf_weight,
     );
-    let mut ocean_temperatures = temperatures(points, terrain);
+    let mut ocean_temperatures = temperatures(points, terrain, config);
     temperature_advection(
         &mut ocean_temperatures, 
         edges, 
@@ -77,6 +74,7 @@ pub fn weather(
         adjacencies, 
         &atmospheric_currents, 
         precipitation_iterations,
+        config,
     );
 
     let ocean_currents = velocities(points, edges, adjacencies, &ocean_currents);
@@ -3,8 +3,6 @@ use bevy::math::Vec3;
 use crate::Terrain;
 use crate::weather::fluid::fluid;
 
-const SHALLOW_WEIGHT: f32 = 0.3;
-
 pub fn ocean(
     points: &Vec<Vec3>,
     half_edges: &Vec<usize>,
@@ -12,11 +10,12 @@ pub fn ocean(
     adjacencies: &Vec<Vec<usize>>, 
     terrain: &Vec<Terrain>,
     iterations: usize,
+    shelf_weight: f32,
 ) -> Vec<f32> {
     let weights: Vec<f32> = terrain.iter().map(|terrain| {
         match terrain {
-            Terrain::Ocean => {1.0},
-            Terrain::Shallow => {SHALLOW_WEIGHT},
+            Terrain::Slope | Terrain::Ocean | Terrain::Trench => {1.0},
+            Terrain::Shelf => {shelf_weight},
             _ => {0.0},
         }
     }).collect();
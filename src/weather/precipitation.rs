@@ -1,27 +1,31 @@
+use crate::config::WeatherConfig;
 use crate::terrain::Terrain;
 use crate::weather::advection::advection;
 
-const DECAY: f32 = 500.0;
-const EVAPORATION: f32 = 0.3;
-
 pub fn precipitation(
     terrain: &Vec<Terrain>,
     edges: &Vec<Vec<usize>>, 
     adjacencies: &Vec<Vec<usize>>, 
     currents: &Vec<f32>,
     iterations: usize,
+    config: &WeatherConfig,
 ) -> Vec<f32> {
     let mut precipitation = vec![0.0].repeat(terrain.len());
-    let decay = 1.0 - DECAY / terrain.len() as f32;
+    let decay = 1.0 - config.rain_decay / terrain.len() as f32;
+    let evaporation = config.evaporation;
 
     advection(&mut precipitation, edges, adjacencies, currents, iterations, |precipitations| {
         for p in 0..terrain.len() {
             match terrain[p] {
-                Terrain::Ocean | Terrain::Shallow => {
-                    precipitations[p] *= 1.0 - EVAPORATION;
-                    precipitations[p] += EVAPORATION;
+                Terrain::Shelf | Terrain::Slope | Terrain::Ocean | Terrain::Trench => {
+                    precipitations[p] *= 1.0 - evaporation;
+                    precipitations[p] += evaporation;
                 },
-                Terrain::Land => precipitations[p] *= decay,
+                Terrain::Highland
+                | Terrain::Hills
+                | Terrain::Land
+                | Terrain::Lake
+                | Terrain::SaltLake => precipitations[p] *= decay,
                 Terrain::Mountain => precipitations[p] = 0.0,
             }
         }
@@ -1,27 +1,38 @@
 use bevy::math::Vec3;
 
+use crate::config::WeatherConfig;
 use crate::terrain::Terrain;
 use crate::weather::advection::advection;
 
-const LAND_TEMPERATURE: f32 = 0.2;
-const LAND_VARIATION: f32 = 0.5;
-
 pub fn temperature(
     y: f32,
 ) -> f32 {
     1.0 - 4.0*y*y + 2.0*y*y*y*y
 }
 
+/// The year-round air temperature over land, blending the temperature of
+/// the latitude with the simulated atmosphere.
+pub fn land_temperature(y: f32, atmospheric_temperature: f32) -> f32 {
+    (temperature(y) + 2.0 * atmospheric_temperature) / 3.0
+}
+
+/// The year-round surface temperature of the sea, blending the temperature
+/// of the latitude with the simulated ocean and atmosphere.
+pub fn sea_temperature(y: f32, ocean_temperature: f32, atmospheric_temperature: f32) -> f32 {
+    (temperature(y) + 2.0 * ocean_temperature + atmospheric_temperature) / 4.0
+}
+
 pub fn temperatures(
     points: &Vec<Vec3>,
     terrain: &Vec<Terrain>,
+    config: &WeatherConfig,
 ) -> Vec<f32> {
     (0..points.len()).map(|p| {
         let temperature = temperature(points[p].y);
-        match terrain[p] {
-            Terrain::Ocean => temperature,
-            Terrain::Shallow => temperature,
-            _ => LAND_TEMPERATURE + LAND_VARIATION * temperature,
+        if terrain[p].is_ocean() {
+            temperature
+        } else {
+            config.land_temperature + config.land_variation * temperature
         }
     }).collect()
 }
@@ -0,0 +1,27 @@
+use std::collections::BTreeSet;
+
+use planet::{Biome, Planet, PlanetConfig};
+
+const SEED: u64 = 42;
+const NUM_POINTS: usize = 5000;
+
+fn planet() -> Planet {
+    Planet::from_seed(SEED, NUM_POINTS, PlanetConfig::default())
+}
+
+#[test]
+fn large_rivers_flood_into_wetland() {
+    let planet = planet();
+    assert!(planet.biomes.contains(&Biome::Wetland));
+}
+
+#[test]
+fn every_koppen_group_occurs() {
+    let planet = planet();
+    // The group is the first letter of the zone's name.
+    let groups: BTreeSet<char> = planet.climate_zone.iter()
+        .flatten()
+        .filter_map(|zone| format!("{zone:?}").chars().next())
+        .collect();
+    assert_eq!(groups, BTreeSet::from(['A', 'B', 'C', 'D', 'E']));
+}
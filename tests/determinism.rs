@@ -0,0 +1,49 @@
+use planet::{Planet, PlanetConfig};
+
+const SEED: u64 = 42;
+const NUM_POINTS: usize = 2000;
+
+macro_rules! assert_fields_eq {
+    ($a:expr, $b:expr, $($field:ident),+ $(,)?) => {
+        $(assert!($a.$field == $b.$field, concat!("`", stringify!($field), "` differs"));)+
+    };
+}
+
+#[test]
+fn same_seed_generates_the_same_planet() {
+    let a = Planet::from_seed(SEED, NUM_POINTS, PlanetConfig::default());
+    let b = Planet::from_seed(SEED, NUM_POINTS, PlanetConfig::default());
+
+    assert_fields_eq!(
+        a, b,
+        seed,
+        config,
+        points,
+        triangles,
+        adjacencies,
+        terrain,
+        biomes,
+        elevation,
+        surface_height,
+        borders,
+        plates,
+        plate_ids,
+        plate_velocities,
+        stress,
+        boundaries,
+        volcanoes,
+        ocean_currents,
+        ocean_temperatures,
+        atmospheric_currents,
+        atmospheric_temperatures,
+        precipitation,
+        monthly_temperatures,
+        monthly_rainfall,
+        climate_zone,
+        downstream,
+        flow,
+        basins,
+        rivers,
+        lakes,
+    );
+}